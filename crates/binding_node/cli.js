@@ -0,0 +1,129 @@
+#!/usr/bin/env node
+const path = require('node:path')
+const { parseArgs } = require('node:util')
+
+const binding = require('./index.js')
+
+const USAGE = `Usage: tsrs <command> [root] [options]
+
+Commands:
+  build          Compile project into output dir
+  unreachable    List files not reachable from entries, exits with 1 if any
+  affected       List project files depend on changed files directly or indirectly
+
+Options:
+  --output <dir>          Override tsconfig outDir
+  --entry <file>          Entry file relative to root, can be repeated
+  --manifest              Write tswc-manifest.json into output dir
+  --graph <json|dot>      Write module graph into output dir
+  --graph-path <file>     Module graph filepath, default is tswc-graph.<format> in output dir
+  --collapse-packages     Collapse modules of the same node_modules package into single node
+  --graph-root <file>     Only export modules reachable from the file relative to root
+  --graph-depth <n>       Max depth from --graph-root
+  --ignore <glob>         Files ignored by unreachable, default is test files, can be repeated
+  --changed <file>        Changed file relative to root for affected, can be repeated
+  --include <glob>        Only list matched files for affected, can be repeated
+  -h, --help              Show this message
+`
+
+const options = {
+  output: { type: 'string' },
+  entry: { type: 'string', multiple: true },
+  manifest: { type: 'boolean' },
+  graph: { type: 'string' },
+  'graph-path': { type: 'string' },
+  'collapse-packages': { type: 'boolean' },
+  'graph-root': { type: 'string' },
+  'graph-depth': { type: 'string' },
+  ignore: { type: 'string', multiple: true },
+  changed: { type: 'string', multiple: true },
+  include: { type: 'string', multiple: true },
+  help: { type: 'boolean', short: 'h' },
+}
+
+/** Options shared by every command */
+function transformOptions(root, values) {
+  return {
+    root: path.resolve(root ?? '.'),
+    output: values.output && path.resolve(values.output),
+    entries: values.entry,
+    optimize: {},
+  }
+}
+
+function graphOptions(values) {
+  const depth = values['graph-depth']
+  if (depth !== undefined && !/^\d+$/.test(depth)) {
+    throw new Error(`--graph-depth expects a number, got ${JSON.stringify(depth)}`)
+  }
+  return {
+    format: values.graph,
+    path: values['graph-path'] && path.resolve(values['graph-path']),
+    collapsePackages: values['collapse-packages'],
+    root: values['graph-root'],
+    depth: depth === undefined ? undefined : Number(depth),
+  }
+}
+
+/** Print diagnostics, returns exit code */
+function report(diagnostics) {
+  for (const { severity, code, message, file, line, column } of diagnostics) {
+    const location = file ? `${file}${line ? `:${line}:${column ?? 0}` : ''}: ` : ''
+    console.error(`${location}${severity}: ${message} [${code}]`)
+  }
+  return diagnostics.some((f) => f.severity === 'error') ? 1 : 0
+}
+
+const commands = {
+  build(root, values) {
+    const { diagnostics } = binding.transform({
+      ...transformOptions(root, values),
+      manifest: values.manifest,
+      graph: values.graph ? graphOptions(values) : undefined,
+    })
+    return report(diagnostics)
+  },
+  unreachable(root, values) {
+    const files = binding.unreachable(transformOptions(root, values), { ignore: values.ignore })
+    for (const { path, size } of files) {
+      console.log(`${path}\t${size}`)
+    }
+    return files.length ? 1 : 0
+  },
+  affected(root, values) {
+    if (!values.changed) {
+      throw new Error('affected expects at least one --changed file')
+    }
+    const files = binding.affected(transformOptions(root, values), {
+      changed: values.changed,
+      include: values.include,
+    })
+    for (const file of files) {
+      console.log(file)
+    }
+    return 0
+  },
+}
+
+function main(argv) {
+  const { values, positionals } = parseArgs({ args: argv, options, allowPositionals: true })
+  const [command, root, ...rest] = positionals
+  if (values.help || !command) {
+    console.log(USAGE)
+    return values.help ? 0 : 1
+  }
+  if (!Object.hasOwn(commands, command)) {
+    throw new Error(`Unknown command ${JSON.stringify(command)}\n\n${USAGE}`)
+  }
+  if (rest.length) {
+    throw new Error(`Unexpected arguments ${rest.join(' ')}`)
+  }
+  return commands[command](root, values)
+}
+
+try {
+  process.exitCode = main(process.argv.slice(2))
+} catch (e) {
+  console.error(e.message)
+  process.exitCode = 1
+}
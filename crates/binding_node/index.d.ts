@@ -4,16 +4,189 @@
 /* auto-generated by NAPI-RS */
 
 export interface TransformOptimizeOptions {
-  /** Optimized packages */
+  /** Optimized packages, replaces presets. `...` is expanded to presets e.g. `['...', 'my-ui']` */
   barrelPackages?: Array<string>
+  /**
+   * Optimize installed packages of built-in list e.g. `@mui/material` and `lodash-es`, default is
+   * false
+   */
+  presets?: boolean
+  /** Rewrite imports through pure barrel files of project to underlying modules, default is false */
+  localBarrels?: boolean
+  /** Globs relative to root, matched files are never optimized as local barrels */
+  excludeBarrels?: Array<string>
+  /** Optimize dependencies declare `sideEffects: false` in their package.json, default is false */
+  sideEffectsFree?: boolean
+  /**
+   * Barrel packages may have side effects skipped by optimization
+   * `warn` (default) optimize and report a warning
+   * `skip` do not optimize and report a warning
+   * `ignore` optimize silently
+   */
+  sideEffects?: string
+  /** Rewrite named imports of packages by templates without resolving them, keyed by package name */
+  modularizeImports?: Record<string, TransformModularizeImportOptions>
+}
+export interface TransformModularizeImportOptions {
+  /**
+   * Template of module path e.g. `lodash/{{member}}`, helpers `camelCase`, `kebabCase`,
+   * `snakeCase`, `upperCase` and `lowerCase` are supported e.g. `@icons/{{kebabCase member}}`
+   */
+  transform: string
+  /** Keep named imports, otherwise they are converted to default imports */
+  skipDefaultConversion?: boolean
+  /** Templates of specific members, keyed by imported name */
+  members?: Record<string, string>
+}
+export interface TransformWorkspacePackageOptions {
+  /**
+   * `inline` (default) compile sources into output dir
+   * `external` keep the bare specifier
+   * `output` rewrite to built output declared by its package.json `main`/`exports`
+   */
+  strategy?: string
+  /** Destination of inlined sources relative to output dir, only works with `inline` strategy */
+  dest?: string
+}
+export interface TransformGraphOptions {
+  /** `json` (default) or `dot` */
+  format?: string
+  /** Output filepath, default is `tswc-graph.json` or `tswc-graph.dot` in output dir */
+  path?: string
+  /** Collapse modules of the same node_modules package into single node */
+  collapsePackages?: boolean
+  /** Only export modules reachable from root module, relative to root */
+  root?: string
+  /** Max depth from root module */
+  depth?: number
+}
+export interface TransformCircularOptions {
+  /** Globs relative to root, cycle is ignored if every module in it matches one of them */
+  allowlist?: Array<string>
+  /** `warning` (default) or `error` */
+  severity?: string
+}
+export interface TransformDependenciesOptions {
+  /** Globs relative to root, devDependencies are allowed in matched files, default is test files e.g. `**\/*.test.*` */
+  test?: Array<string>
+}
+export interface TransformBoundaryRule {
+  /** Globs of importers relative to root, every module by default */
+  from?: Array<string>
+  /** Globs of targets relative to root, or package names e.g. `pg` */
+  to: Array<string>
+  /** Globs of importers relative to root allowed to import targets */
+  except?: Array<string>
+  /** Reason appended to diagnostic message */
+  message?: string
+  /** `error` (default) or `warning` */
+  severity?: string
+}
+export interface TransformApiOptions {
+  /** Baseline filepath relative to root or absolute, default is `tswc-api.json` in root */
+  baseline?: string
+  /** Overwrite baseline with current API surface instead of comparing */
+  update?: boolean
+  /** Throw an error if API surface is broken, default is true. Set false to only report diagnostics */
+  failOnBreaking?: boolean
 }
 export interface TransformOptions {
   root: string
   output?: string
   externals?: Array<string>
   exclude?: Array<string>
+  /** Entry files relative to root, only files reachable from them are compiled */
+  entries?: Array<string>
   modules?: Array<string>
+  /** Write `tswc-manifest.json` into output dir after build */
+  manifest?: boolean
+  /** Write module graph into output dir after build */
+  graph?: TransformGraphOptions
+  /** Report import cycles between project modules */
+  circular?: TransformCircularOptions
+  /** Report undeclared, misplaced and unused package.json dependencies */
+  dependencies?: TransformDependenciesOptions
+  /** Report packages resolved to several copies in node_modules */
+  duplicates?: boolean
+  /** Module boundary rules checked against every import, e.g. `src/ui/**` may not import `src/server/**` */
+  rules?: Array<TransformBoundaryRule>
+  /** Compare exports of public entries with baseline, removed or changed exports are errors */
+  api?: TransformApiOptions
+  /** Strategies of `paths` mapped workspace packages, keyed by package name */
+  workspacePackages?: Record<string, TransformWorkspacePackageOptions>
   /** Optimized options */
   optimize: TransformOptimizeOptions
 }
-export declare function transform(options: TransformOptions): void
+export interface TransformDiagnostic {
+  code: string
+  /** `warning` or `error` */
+  severity: string
+  message: string
+  file?: string
+  line?: number
+  column?: number
+}
+export interface TransformResult {
+  diagnostics: Array<TransformDiagnostic>
+}
+export interface UnreachableOptions {
+  /** Globs relative to root, default ignores test files e.g. `**\/*.test.*` */
+  ignore?: Array<string>
+}
+export interface UnreachableFileResult {
+  /** Filepath relative to root */
+  path: string
+  /** File size in bytes */
+  size: number
+}
+export interface AffectedOptions {
+  /** Changed files, relative to root or absolute */
+  changed: Array<string>
+  /** Globs relative to root, only matched files are returned, e.g. `**\/*.test.ts` */
+  include?: Array<string>
+}
+export interface ParseImport {
+  /** Module specifier, none for dynamic import of non string literal and `import.meta` */
+  n?: string
+  /** `1` static, `2` dynamic, `3` import.meta, `4` static source phase, `5` dynamic source phase */
+  t: number
+  /** Start of module specifier */
+  s: number
+  /** End of module specifier */
+  e: number
+  /** Start of import statement */
+  ss: number
+  /** End of import statement */
+  se: number
+  /** Start of dynamic import bracket, `-1` for static import, `-2` for import.meta */
+  d: number
+  /** Start of import attributes, `-1` if there are none */
+  a: number
+}
+export interface ParseExport {
+  /** Exported name */
+  n: string
+  /** Local name, none for re-exports */
+  ln?: string
+  /** Start of exported name */
+  s: number
+  /** End of exported name */
+  e: number
+  /** Start of local name, `-1` if there is none */
+  ls: number
+  /** End of local name, `-1` if there is none */
+  le: number
+}
+export interface ParseResult {
+  imports: Array<ParseImport>
+  exports: Array<ParseExport>
+  /** Module only contains imports and re-exports */
+  facade: boolean
+  hasModuleSyntax: boolean
+}
+export declare function transform(options: TransformOptions): TransformResult
+export declare function unreachable(options: TransformOptions, unreachableOptions?: UnreachableOptions | undefined | null): Array<UnreachableFileResult>
+export declare function unusedExports(options: TransformOptions): Array<TransformDiagnostic>
+export declare function check(options: TransformOptions): TransformResult
+export declare function affected(options: TransformOptions, affectedOptions: AffectedOptions): Array<string>
+export declare function parse(code: string, filename: string): ParseResult
@@ -310,6 +310,11 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { transform } = nativeBinding
+const { transform, unreachable, unusedExports, check, affected, parse } = nativeBinding
 
 module.exports.transform = transform
+module.exports.unreachable = unreachable
+module.exports.unusedExports = unusedExports
+module.exports.check = check
+module.exports.affected = affected
+module.exports.parse = parse
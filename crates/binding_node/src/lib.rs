@@ -1,10 +1,46 @@
 #![deny(clippy::all)]
-use tswc::apis::{transform as tswc, TransformOptions};
+use tswc::apis::{
+  affected as tswc_affected, check as tswc_check, parse as tswc_parse, transform as tswc,
+  unreachable as tswc_unreachable, unused_exports as tswc_unused_exports, AffectedOptions,
+  ParseResult, TransformDiagnostic, TransformOptions, TransformResult, UnreachableFileResult,
+  UnreachableOptions,
+};
 
 #[macro_use]
 extern crate napi_derive;
 
 #[napi]
-pub fn transform(options: TransformOptions) {
-  return tswc(options);
+pub fn transform(options: TransformOptions) -> napi::Result<TransformResult> {
+  tswc(options)
+}
+
+#[napi]
+pub fn unreachable(
+  options: TransformOptions,
+  unreachable_options: Option<UnreachableOptions>,
+) -> napi::Result<Vec<UnreachableFileResult>> {
+  tswc_unreachable(options, unreachable_options)
+}
+
+#[napi]
+pub fn unused_exports(options: TransformOptions) -> napi::Result<Vec<TransformDiagnostic>> {
+  tswc_unused_exports(options)
+}
+
+#[napi]
+pub fn check(options: TransformOptions) -> napi::Result<TransformResult> {
+  tswc_check(options)
+}
+
+#[napi]
+pub fn affected(
+  options: TransformOptions,
+  affected_options: AffectedOptions,
+) -> napi::Result<Vec<String>> {
+  tswc_affected(options, affected_options)
+}
+
+#[napi]
+pub fn parse(code: String, filename: String) -> napi::Result<ParseResult> {
+  tswc_parse(code, filename)
 }
@@ -0,0 +1,51 @@
+import { spawnSync } from 'node:child_process'
+import fs from 'node:fs'
+import os from 'node:os'
+import path from 'node:path'
+
+import {
+  describe,
+  expect,
+  it,
+} from 'vitest'
+
+const cli = path.join(__dirname, '../cli.js')
+const fixtures = path.join(__dirname, '../../tswc/tests/fixtures')
+
+const run = (...args: string[]) => spawnSync(process.execPath, [cli, ...args], { encoding: 'utf8' })
+
+describe('cli', () => {
+  it('build with graph', () => {
+    const output = fs.mkdtempSync(path.join(os.tmpdir(), 'tsrs-cli-'))
+    const { status } = run(
+      'build',
+      path.join(fixtures, 'graph'),
+      '--output', output,
+      '--entry', 'src/index.ts',
+      '--graph', 'dot',
+      '--collapse-packages',
+    )
+    expect(status).toBe(0)
+    const dot = fs.readFileSync(path.join(output, 'tswc-graph.dot'), 'utf8')
+    expect(dot).toContain('"pkg" [label="pkg" shape=component];')
+    fs.rmSync(output, { recursive: true })
+  })
+
+  it('unreachable', () => {
+    const { status, stdout } = run('unreachable', path.join(fixtures, 'graph'), '--entry', 'src/a.ts')
+    expect(status).toBe(1)
+    expect(stdout.split('\n').map((f) => f.split('\t')[0])).toEqual(['src/index.ts', 'src/lazy.ts', ''])
+  })
+
+  it('affected', () => {
+    const { status, stdout } = run('affected', path.join(fixtures, 'graph'), '--changed', 'src/b.ts')
+    expect(status).toBe(0)
+    expect(stdout).toBe('src/a.ts\nsrc/b.ts\nsrc/index.ts\n')
+  })
+
+  it('unknown command', () => {
+    const { status, stderr } = run('bundle')
+    expect(status).toBe(1)
+    expect(stderr).toContain('Unknown command "bundle"')
+  })
+})
@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::exports::{
+  imported_name, is_export_star, resolve_export, resolve_export_src, ExportResolution, ALL,
+};
+use super::{Diagnostic, Severity};
+use crate::compiler::ModuleGraph;
+use crate::utils::ExportKind;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ApiExport {
+  pub name: String,
+  pub kind: ExportKind,
+}
+
+/// Exported names of public entries, keyed by entry filepath relative to root
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiSurface {
+  pub entries: BTreeMap<String, Vec<ApiExport>>,
+}
+
+/// Names declared by module and modules of its `export *`
+fn collect_names(
+  mg: &ModuleGraph,
+  id: &str,
+  visited: &mut HashSet<String>,
+  names: &mut BTreeSet<String>,
+) {
+  if !visited.insert(id.to_string()) {
+    return;
+  }
+  let Some(m) = mg.modules.get(id) else {
+    return;
+  };
+  for export in &m.exports {
+    if !is_export_star(export) {
+      names.insert(export.n.clone());
+    } else if let Some(to) = resolve_export_src(mg, id, export) {
+      collect_names(mg, &to, visited, names);
+    }
+  }
+}
+
+/// Kind of export `name` of module, `None` if it is not exported
+fn export_kind(mg: &ModuleGraph, id: &str, name: &str) -> Option<ExportKind> {
+  // `export type { a } from 'b'` hides kind of the declaration
+  let own = mg.modules.get(id)?.exports.iter().find(|f| f.n == name);
+  if own.is_some_and(|f| f.kind == ExportKind::Type) {
+    return Some(ExportKind::Type);
+  }
+  match resolve_export(mg, id, name, &mut HashSet::new()) {
+    ExportResolution::Found(origin, origin_name) => {
+      let export = mg.modules[&origin]
+        .exports
+        .iter()
+        .find(|f| !is_export_star(f) && f.n == origin_name)?;
+      if export.src.is_some() && imported_name(export) == ALL {
+        return Some(ExportKind::Namespace);
+      }
+      Some(export.kind)
+    }
+    // Re-exported from node_modules
+    ExportResolution::Unknown => own.map(|_| ExportKind::Value),
+    ExportResolution::Missing | ExportResolution::Ambiguous(_) => None,
+  }
+}
+
+impl ApiSurface {
+  pub fn new(mg: &ModuleGraph, entries: &[String]) -> ApiSurface {
+    let mut surface = ApiSurface::default();
+    for id in entries {
+      let mut names = BTreeSet::new();
+      collect_names(mg, id, &mut HashSet::new(), &mut names);
+      let exports = names
+        .into_iter()
+        .filter_map(|name| {
+          let kind = export_kind(mg, id, &name)?;
+          Some(ApiExport { name, kind })
+        })
+        .collect();
+      surface.entries.insert(mg.relative_to_root(id), exports);
+    }
+    surface
+  }
+  pub fn from_json(content: &str) -> Result<ApiSurface, String> {
+    serde_json::from_str(content).map_err(|err| err.to_string())
+  }
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).expect("Failed to serialize API surface")
+  }
+}
+
+/// Report exports of `baseline` removed or changed by `current`, added exports are compatible
+pub fn check_api_surface(
+  root: &Path,
+  baseline: &ApiSurface,
+  current: &ApiSurface,
+) -> Vec<Diagnostic> {
+  let mut diagnostics = vec![];
+  for (entry, exports) in &baseline.entries {
+    let file = Some(root.join(entry).to_str().unwrap_or_default().to_string());
+    let Some(current_exports) = current.entries.get(entry) else {
+      diagnostics.push(Diagnostic {
+        code: "api-removed",
+        severity: Severity::Error,
+        message: format!("Public entry {} is removed", entry),
+        file: None,
+        loc: None,
+      });
+      continue;
+    };
+    for export in exports {
+      let (code, message) = match current_exports.iter().find(|f| f.name == export.name) {
+        None => (
+          "api-removed",
+          format!("Export `{}` of {} is removed", export.name, entry),
+        ),
+        Some(found) if found.kind != export.kind => (
+          "api-changed",
+          format!(
+            "Export `{}` of {} is changed from {} to {}",
+            export.name,
+            entry,
+            export.kind.as_str(),
+            found.kind.as_str()
+          ),
+        ),
+        Some(_) => continue,
+      };
+      diagnostics.push(Diagnostic {
+        code,
+        severity: Severity::Error,
+        message,
+        file: file.clone(),
+        loc: None,
+      });
+    }
+  }
+  diagnostics
+}
@@ -0,0 +1,62 @@
+use glob::Pattern;
+
+use super::Diagnostic;
+use crate::compiler::{Edge, ModuleGraph, ModuleKind};
+use crate::config::BoundaryRule;
+use crate::utils::package_name_of_specifier;
+
+fn matches_any(globs: &[String], candidates: &[String]) -> bool {
+  globs
+    .iter()
+    .filter_map(|f| Pattern::new(f).ok())
+    .any(|p| candidates.iter().any(|c| p.matches(c)))
+}
+
+/// Names of import target matched by rules, filepath relative to root for project files,
+/// specifier and package name for packages
+fn target_candidates(mg: &ModuleGraph, edge: &Edge) -> Vec<String> {
+  let mut candidates = vec![edge.specifier.clone()];
+  if let Some(package) = package_name_of_specifier(&edge.specifier) {
+    candidates.push(package);
+  }
+  let is_file = mg
+    .modules
+    .get(&edge.to)
+    .is_some_and(|m| matches!(m.kind(), ModuleKind::Script | ModuleKind::Asset));
+  if is_file {
+    candidates.push(mg.relative_to_root(&edge.to));
+  }
+  candidates
+}
+
+/// Check import against module boundary rules of config
+pub fn check_boundaries(mg: &ModuleGraph, edge: &Edge) -> Vec<Diagnostic> {
+  let rules: &[BoundaryRule] = &mg.config.options.rules;
+  if rules.is_empty() {
+    return vec![];
+  }
+  let from = vec![mg.relative_to_root(&edge.from)];
+  let targets = target_candidates(mg, edge);
+  rules
+    .iter()
+    .filter(|rule| rule.from.is_empty() || matches_any(&rule.from, &from))
+    .filter(|rule| !matches_any(&rule.except, &from))
+    .filter(|rule| matches_any(&rule.to, &targets))
+    .map(|rule| {
+      let mut message = format!(
+        "Import of `{}` from {} is not allowed",
+        edge.specifier, from[0]
+      );
+      if let Some(reason) = &rule.message {
+        message = format!("{}: {}", message, reason);
+      }
+      Diagnostic {
+        code: "boundary-violation",
+        severity: rule.severity,
+        message,
+        file: Some(edge.from.clone()),
+        loc: edge.loc,
+      }
+    })
+    .collect()
+}
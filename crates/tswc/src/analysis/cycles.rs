@@ -0,0 +1,172 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use glob::Pattern;
+
+use super::{Diagnostic, Severity};
+use crate::compiler::{Edge, EdgeKind, ModuleGraph, ModuleKind};
+
+#[derive(Debug, Clone, Default)]
+pub struct CircularOptions {
+  /// Globs relative to root, cycle is ignored if every module in it matches one of them
+  pub allowlist: Vec<String>,
+  pub severity: Severity,
+}
+
+/// Static import edges between project modules, evaluated at runtime
+fn is_runtime_edge(mg: &ModuleGraph, edge: &Edge) -> bool {
+  if !matches!(edge.kind, EdgeKind::Static | EdgeKind::ReExport) {
+    return false;
+  }
+  let is_project = |id: &str| {
+    mg.modules
+      .get(id)
+      .is_some_and(|m| m.kind() == ModuleKind::Script)
+  };
+  is_project(&edge.from) && is_project(&edge.to)
+}
+
+/// Tarjan's strongly connected components, only components contain cycle are returned
+pub fn strongly_connected_components(mg: &ModuleGraph) -> Vec<Vec<String>> {
+  let mut nodes: Vec<&str> = mg.modules.keys().map(|f| f.as_str()).collect();
+  nodes.sort();
+  let successors = |id: &str| -> Vec<&str> {
+    mg.dependencies(id)
+      .into_iter()
+      .filter(|e| is_runtime_edge(mg, e))
+      .map(|e| e.to.as_str())
+      .collect()
+  };
+
+  let mut index: HashMap<&str, usize> = HashMap::new();
+  let mut lowlink: HashMap<&str, usize> = HashMap::new();
+  let mut on_stack: HashSet<&str> = HashSet::new();
+  let mut stack: Vec<&str> = vec![];
+  let mut components = vec![];
+  let mut counter = 0;
+
+  for start in nodes {
+    if index.contains_key(start) {
+      continue;
+    }
+    // (node, successors, next successor position), iterative to avoid stack overflow on deep graphs
+    let mut work: Vec<(&str, Vec<&str>, usize)> = vec![(start, successors(start), 0)];
+    index.insert(start, counter);
+    lowlink.insert(start, counter);
+    counter += 1;
+    stack.push(start);
+    on_stack.insert(start);
+    while let Some((node, succ, pos)) = work.last_mut() {
+      let node = *node;
+      if let Some(next) = succ.get(*pos).copied() {
+        *pos += 1;
+        if !index.contains_key(next) {
+          index.insert(next, counter);
+          lowlink.insert(next, counter);
+          counter += 1;
+          stack.push(next);
+          on_stack.insert(next);
+          work.push((next, successors(next), 0));
+        } else if on_stack.contains(next) {
+          let low = lowlink[node].min(index[next]);
+          lowlink.insert(node, low);
+        }
+        continue;
+      }
+      work.pop();
+      if let Some((parent, _, _)) = work.last() {
+        let low = lowlink[parent].min(lowlink[node]);
+        lowlink.insert(parent, low);
+      }
+      if lowlink[node] == index[node] {
+        let mut component = vec![];
+        while let Some(top) = stack.pop() {
+          on_stack.remove(top);
+          component.push(top.to_string());
+          if top == node {
+            break;
+          }
+        }
+        let is_self_loop = || successors(node).contains(&node);
+        if component.len() > 1 || is_self_loop() {
+          component.sort();
+          components.push(component);
+        }
+      }
+    }
+  }
+  components
+}
+
+/// Shortest cycle from the first module of component back to itself
+fn find_cycle<'a>(mg: &'a ModuleGraph, component: &[String]) -> Vec<&'a Edge> {
+  let members: HashSet<&str> = component.iter().map(|f| f.as_str()).collect();
+  let start = component[0].as_str();
+  let mut previous: HashMap<&str, &Edge> = HashMap::new();
+  let mut queue = VecDeque::from([start]);
+  while let Some(current) = queue.pop_front() {
+    for edge in mg.dependencies(current) {
+      if !is_runtime_edge(mg, edge) || !members.contains(edge.to.as_str()) {
+        continue;
+      }
+      if edge.to == start {
+        let mut chain = vec![edge];
+        let mut node = current;
+        while node != start {
+          let e = previous[node];
+          chain.push(e);
+          node = e.from.as_str();
+        }
+        chain.reverse();
+        return chain;
+      }
+      if !previous.contains_key(edge.to.as_str()) {
+        previous.insert(edge.to.as_str(), edge);
+        queue.push_back(edge.to.as_str());
+      }
+    }
+  }
+  vec![]
+}
+
+/// Report runtime import cycles between project modules
+pub fn check_circular(mg: &ModuleGraph, options: &CircularOptions) -> Vec<Diagnostic> {
+  let allowlist: Vec<Pattern> = options
+    .allowlist
+    .iter()
+    .filter_map(|f| Pattern::new(f).ok())
+    .collect();
+  let mut diagnostics = vec![];
+  for component in strongly_connected_components(mg) {
+    let allowed = !allowlist.is_empty()
+      && component.iter().all(|id| {
+        let path = mg.relative_to_root(id);
+        allowlist.iter().any(|p| p.matches(&path))
+      });
+    if allowed {
+      continue;
+    }
+    let chain = find_cycle(mg, &component);
+    let Some(first) = chain.first() else {
+      continue;
+    };
+    let mut hops: Vec<String> = chain
+      .iter()
+      .map(|e| {
+        let path = mg.relative_to_root(&e.from);
+        match e.loc {
+          Some(loc) => format!("{}:{}", path, loc.line),
+          None => path,
+        }
+      })
+      .collect();
+    hops.push(mg.relative_to_root(&first.from));
+    diagnostics.push(Diagnostic {
+      code: "circular-dependency",
+      severity: options.severity,
+      message: format!("Circular dependency: {}", hops.join(" -> ")),
+      file: Some(first.from.clone()),
+      loc: first.loc,
+    });
+  }
+  diagnostics
+}
@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use glob::Pattern;
+
+use super::{Diagnostic, Severity};
+use crate::compiler::{Edge, ModuleGraph, ModuleKind};
+use crate::resolver::PackageJson;
+use crate::utils::{find_up_package_root, package_name_of_specifier};
+
+const RUNTIME_FIELDS: [&str; 3] = ["dependencies", "peerDependencies", "optionalDependencies"];
+
+#[derive(Debug, Clone, Default)]
+pub struct DependenciesOptions {
+  /// Globs relative to root, devDependencies are allowed in matched files
+  pub test: Vec<String>,
+}
+
+/// Bare imports from project modules with package name, grouped by importer package root
+fn collect_imports(mg: &ModuleGraph) -> BTreeMap<PathBuf, Vec<(&Edge, String)>> {
+  let mut imports: BTreeMap<PathBuf, Vec<(&Edge, String)>> = BTreeMap::new();
+  for edge in &mg.edges {
+    let (Some(from), Some(to)) = (mg.modules.get(&edge.from), mg.modules.get(&edge.to)) else {
+      continue;
+    };
+    if from.kind() != ModuleKind::Script || to.built_in {
+      continue;
+    }
+    let Some(package) = package_name_of_specifier(&edge.specifier) else {
+      continue;
+    };
+    let Some(root) = find_up_package_root(edge.from.as_ref()) else {
+      continue;
+    };
+    imports.entry(root).or_default().push((edge, package));
+  }
+  imports
+}
+
+/// Report packages imported but not declared in package.json of importer, devDependencies
+/// imported from non-test files and dependencies never imported
+pub fn check_dependencies(mg: &ModuleGraph, options: &DependenciesOptions) -> Vec<Diagnostic> {
+  let test: Vec<Pattern> = options
+    .test
+    .iter()
+    .filter_map(|f| Pattern::new(f).ok())
+    .collect();
+  let mut imports = collect_imports(mg);
+  // Unused dependencies of root package are reported even if nothing is imported
+  imports.entry(mg.config.options.root.clone()).or_default();
+  let mut diagnostics = vec![];
+  for (root, edges) in imports {
+    let Some(pkg) = PackageJson::read(&root) else {
+      continue;
+    };
+    let runtime: BTreeSet<String> = RUNTIME_FIELDS
+      .iter()
+      .flat_map(|f| pkg.dependencies(f))
+      .collect();
+    let dev: BTreeSet<String> = pkg.dependencies("devDependencies").into_iter().collect();
+    let manifest = root
+      .join("package.json")
+      .to_str()
+      .unwrap_or_default()
+      .to_string();
+    let manifest_relative = mg.relative_to_root(&manifest);
+    let mut imported: BTreeSet<&str> = BTreeSet::new();
+    for (edge, package) in &edges {
+      imported.insert(package);
+      // Only packages installed into node_modules are checked, e.g. `paths` alias are skipped
+      let is_installed = mg
+        .modules
+        .get(&edge.to)
+        .is_some_and(|m| m.is_node_modules || m.external);
+      // Self reference by package name
+      if !is_installed || pkg.name() == Some(package.as_str()) || runtime.contains(package) {
+        continue;
+      }
+      let path = mg.relative_to_root(&edge.from);
+      let (code, severity, message) = if !dev.contains(package) {
+        (
+          "undeclared-dependency",
+          Severity::Error,
+          format!(
+            "`{}` is imported but not declared in {}",
+            package, manifest_relative
+          ),
+        )
+      } else if !test.iter().any(|p| p.matches(&path)) {
+        (
+          "dev-dependency-in-runtime",
+          Severity::Warning,
+          format!(
+            "`{}` is declared in devDependencies of {} but imported from runtime code",
+            package, manifest_relative
+          ),
+        )
+      } else {
+        continue;
+      };
+      diagnostics.push(Diagnostic {
+        code,
+        severity,
+        message,
+        file: Some(edge.from.clone()),
+        loc: edge.loc,
+      });
+    }
+    for package in pkg.dependencies("dependencies") {
+      if package.starts_with("@types/") || imported.contains(package.as_str()) {
+        continue;
+      }
+      diagnostics.push(Diagnostic {
+        code: "unused-dependency",
+        severity: Severity::Warning,
+        message: format!(
+          "`{}` is declared in dependencies of {} but never imported",
+          package, manifest_relative
+        ),
+        file: Some(manifest.clone()),
+        loc: None,
+      });
+    }
+  }
+  diagnostics
+}
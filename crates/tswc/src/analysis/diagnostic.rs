@@ -0,0 +1,28 @@
+use crate::compiler::Location;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Severity {
+  #[default]
+  Warning,
+  Error,
+}
+
+impl Severity {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Severity::Warning => "warning",
+      Severity::Error => "error",
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+  /// Kebab case rule name, e.g. `circular-dependency`
+  pub code: &'static str,
+  pub severity: Severity,
+  pub message: String,
+  /// Absolute filepath diagnostic belongs to
+  pub file: Option<String>,
+  pub loc: Option<Location>,
+}
@@ -0,0 +1,113 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use super::{Diagnostic, Severity};
+use crate::compiler::{Edge, ModuleGraph};
+use crate::resolver::PackageJson;
+use crate::utils::package_name_of_path;
+
+/// A copy of package installed in node_modules
+struct PackageCopy {
+  root: String,
+  version: String,
+  modules: Vec<String>,
+}
+
+/// Directory of package inside node_modules, e.g. `/node_modules/.pnpm/react@18.2.0/node_modules/react`
+fn package_root_of_path(path: &str, name: &str) -> Option<String> {
+  let (prefix, _) = path.rsplit_once("node_modules/")?;
+  Some(format!("{}node_modules/{}", prefix, name))
+}
+
+/// Shortest importer chain from an entry or a module not imported by others to one of `targets`
+fn importer_chain<'a>(mg: &'a ModuleGraph, targets: &[String]) -> Vec<&'a Edge> {
+  let mut next: HashMap<&str, &Edge> = HashMap::new();
+  let mut visited: HashSet<&str> = targets.iter().map(|f| f.as_str()).collect();
+  let mut queue: VecDeque<&str> = targets.iter().map(|f| f.as_str()).collect();
+  while let Some(current) = queue.pop_front() {
+    let dependents = mg.dependents(current);
+    let is_entry = mg.modules.get(current).is_some_and(|m| m.is_entry);
+    if next.contains_key(current) && (is_entry || dependents.is_empty()) {
+      let mut chain = vec![];
+      let mut node = current;
+      while let Some(edge) = next.get(node) {
+        chain.push(*edge);
+        node = edge.to.as_str();
+      }
+      return chain;
+    }
+    for edge in dependents {
+      if visited.insert(edge.from.as_str()) {
+        next.insert(edge.from.as_str(), edge);
+        queue.push_back(edge.from.as_str());
+      }
+    }
+  }
+  vec![]
+}
+
+/// Report packages resolved to several copies in node_modules, with importer chain of each copy
+pub fn check_duplicates(mg: &ModuleGraph) -> Vec<Diagnostic> {
+  let mut packages: BTreeMap<String, BTreeMap<String, PackageCopy>> = BTreeMap::new();
+  for (id, m) in &mg.modules {
+    if !m.is_node_modules || m.abs_path.is_empty() {
+      continue;
+    }
+    let Some(name) = package_name_of_path(&m.abs_path) else {
+      continue;
+    };
+    let Some(root) = package_root_of_path(&m.abs_path, &name) else {
+      continue;
+    };
+    let copies = packages.entry(name).or_default();
+    let copy = copies.entry(root.clone()).or_insert_with(|| {
+      let version = PackageJson::read(root.as_ref())
+        .and_then(|f| f.version().map(|f| f.to_string()))
+        .unwrap_or_else(|| "unknown".into());
+      PackageCopy {
+        root,
+        version,
+        modules: vec![],
+      }
+    });
+    copy.modules.push(id.clone());
+  }
+  let mut diagnostics = vec![];
+  for (name, copies) in packages {
+    if copies.len() < 2 {
+      continue;
+    }
+    let mut first_edge = None;
+    let details: Vec<String> = copies
+      .values()
+      .map(|copy| {
+        let chain = importer_chain(mg, &copy.modules);
+        if first_edge.is_none() {
+          first_edge = chain.first().copied();
+        }
+        let mut hops: Vec<String> = chain.iter().map(|e| mg.relative_to_root(&e.from)).collect();
+        if let Some(last) = chain.last() {
+          hops.push(last.specifier.clone());
+        }
+        format!(
+          "{} at {} ({})",
+          copy.version,
+          mg.relative_to_root(&copy.root),
+          hops.join(" -> ")
+        )
+      })
+      .collect();
+    diagnostics.push(Diagnostic {
+      code: "duplicate-package",
+      severity: Severity::Warning,
+      message: format!(
+        "`{}` is resolved to {} copies: {}",
+        name,
+        copies.len(),
+        details.join(", ")
+      ),
+      file: first_edge.map(|e| e.from.clone()),
+      loc: first_edge.and_then(|e| e.loc),
+    });
+  }
+  diagnostics
+}
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use crate::compiler::{EdgeKind, ModuleGraph, ModuleKind};
+use crate::utils::ExportSpecifier;
+
+/// Namespace of module, all exports are used
+pub const ALL: &str = "*";
+
+/// Resolved module id of export `src`
+pub fn resolve_export_src(mg: &ModuleGraph, id: &str, export: &ExportSpecifier) -> Option<String> {
+  let src = export.src.as_ref()?;
+  mg.dependencies(id)
+    .into_iter()
+    .find(|e| e.kind == EdgeKind::ReExport && &e.specifier == src)
+    .map(|e| e.to.clone())
+}
+
+/// `export * from 'b'`
+pub fn is_export_star(export: &ExportSpecifier) -> bool {
+  export.n == ALL
+}
+
+/// Name exported by `src`, namespace if it is `export * as a from 'b'` or re-exported namespace import
+pub fn imported_name(export: &ExportSpecifier) -> &str {
+  match export.ln.as_deref() {
+    Some(ln) if !ln.is_empty() => ln,
+    _ => ALL,
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportResolution {
+  /// (module id, name) of the declaration
+  Found(String, String),
+  /// Exports of target are unknown, e.g. node_modules or module without module syntax
+  Unknown,
+  Missing,
+  /// Declared by multiple modules through `export *`
+  Ambiguous(Vec<String>),
+}
+
+/// Find declaration of `name` exported by module, following re-exports and `export *` chains
+pub fn resolve_export(
+  mg: &ModuleGraph,
+  id: &str,
+  name: &str,
+  visited: &mut HashSet<(String, String)>,
+) -> ExportResolution {
+  let Some(m) = mg.modules.get(id) else {
+    return ExportResolution::Unknown;
+  };
+  if m.kind() != ModuleKind::Script || !m.used || m.exports.is_empty() {
+    return ExportResolution::Unknown;
+  }
+  if !visited.insert((id.to_string(), name.to_string())) {
+    return ExportResolution::Missing;
+  }
+  if let Some(export) = m.exports.iter().find(|f| !is_export_star(f) && f.n == name) {
+    if export.src.is_none() || imported_name(export) == ALL {
+      return ExportResolution::Found(id.to_string(), name.to_string());
+    }
+    return match resolve_export_src(mg, id, export) {
+      Some(to) => resolve_export(mg, &to, imported_name(export), visited),
+      None => ExportResolution::Unknown,
+    };
+  }
+  // `default` is never re-exported by `export *`
+  if name == "default" {
+    return ExportResolution::Missing;
+  }
+  let mut origins: Vec<(String, String)> = vec![];
+  for export in m.exports.iter().filter(|f| is_export_star(f)) {
+    let Some(to) = resolve_export_src(mg, id, export) else {
+      return ExportResolution::Unknown;
+    };
+    match resolve_export(mg, &to, name, visited) {
+      ExportResolution::Found(origin, origin_name) => {
+        if !origins.contains(&(origin.clone(), origin_name.clone())) {
+          origins.push((origin, origin_name));
+        }
+      }
+      ExportResolution::Missing => {}
+      other => return other,
+    }
+  }
+  match origins.len() {
+    0 => ExportResolution::Missing,
+    1 => {
+      let (origin, origin_name) = origins.remove(0);
+      ExportResolution::Found(origin, origin_name)
+    }
+    _ => ExportResolution::Ambiguous(origins.into_iter().map(|(f, _)| f).collect()),
+  }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+
+use super::exports::{resolve_export, ExportResolution, ALL};
+use super::{Diagnostic, Severity};
+use crate::compiler::{EdgeKind, ModuleGraph, ModuleKind};
+
+/// Verify names imported or re-exported between project modules are exported by the target
+pub fn check_imports(mg: &ModuleGraph) -> Vec<Diagnostic> {
+  let mut diagnostics = vec![];
+  let mut edges: Vec<_> = mg
+    .edges
+    .iter()
+    .filter(|e| matches!(e.kind, EdgeKind::Static | EdgeKind::ReExport))
+    .filter(|e| {
+      mg.modules
+        .get(&e.from)
+        .is_some_and(|m| m.kind() == ModuleKind::Script)
+    })
+    .collect();
+  edges.sort_by(|a, b| (&a.from, &a.specifier).cmp(&(&b.from, &b.specifier)));
+  // Name of import binding may be checked twice by import and re-export
+  let mut checked: HashSet<(&str, &str, &str)> = HashSet::new();
+  for edge in edges {
+    for name in edge.names.iter().filter(|f| f.as_str() != ALL) {
+      if !checked.insert((&edge.from, &edge.to, name)) {
+        continue;
+      }
+      let resolution = resolve_export(mg, &edge.to, name, &mut HashSet::new());
+      let target = mg.relative_to_root(&edge.to);
+      let (code, message) = match resolution {
+        ExportResolution::Found(..) | ExportResolution::Unknown => continue,
+        ExportResolution::Missing if name == "default" => (
+          "missing-default-export",
+          format!("{} does not have a default export", target),
+        ),
+        ExportResolution::Missing => (
+          "missing-export",
+          format!("`{}` is not exported by {}", name, target),
+        ),
+        ExportResolution::Ambiguous(origins) => {
+          let origins: Vec<String> = origins.iter().map(|f| mg.relative_to_root(f)).collect();
+          (
+            "ambiguous-export",
+            format!(
+              "`{}` of {} is ambiguous, it is exported by `export *` of {}",
+              name,
+              target,
+              origins.join(", ")
+            ),
+          )
+        }
+      };
+      diagnostics.push(Diagnostic {
+        code,
+        severity: Severity::Error,
+        message,
+        file: Some(edge.from.clone()),
+        loc: edge.loc,
+      });
+    }
+  }
+  diagnostics
+}
@@ -0,0 +1,19 @@
+mod api_surface;
+mod boundaries;
+mod cycles;
+mod dependencies;
+mod diagnostic;
+mod duplicates;
+mod exports;
+mod imports;
+mod unreachable;
+mod unused_exports;
+pub use api_surface::{check_api_surface, ApiSurface};
+pub use boundaries::check_boundaries;
+pub use cycles::{check_circular, CircularOptions};
+pub use dependencies::{check_dependencies, DependenciesOptions};
+pub use diagnostic::{Diagnostic, Severity};
+pub use duplicates::check_duplicates;
+pub use imports::check_imports;
+pub use unreachable::{check_unreachable, find_unreachable, UnreachableFile};
+pub use unused_exports::check_unused_exports;
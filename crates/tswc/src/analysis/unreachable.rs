@@ -0,0 +1,65 @@
+use std::fs;
+use std::path::PathBuf;
+
+use glob::Pattern;
+use sugar_path::SugarPath;
+
+use super::{Diagnostic, Severity};
+use crate::compiler::ModuleGraph;
+
+/// Searched files under root which are never added into module graph
+pub fn unreachable_files(mg: &ModuleGraph) -> Vec<PathBuf> {
+  let mut files: Vec<PathBuf> = mg
+    .config
+    .files
+    .iter()
+    .map(|f| f.as_path().absolutize())
+    .filter(|f| !mg.modules.contains_key(f.to_str().unwrap_or_default()))
+    .collect();
+  files.sort();
+  files
+}
+
+#[derive(Debug, Clone)]
+pub struct UnreachableFile {
+  /// Filepath relative to root
+  pub path: String,
+  /// File size in bytes
+  pub size: u64,
+}
+
+/// Unreachable files with sizes, files match one of `ignore` globs relative to root are skipped
+pub fn find_unreachable(mg: &ModuleGraph, ignore: &[String]) -> Vec<UnreachableFile> {
+  let ignore: Vec<Pattern> = ignore.iter().filter_map(|f| Pattern::new(f).ok()).collect();
+  unreachable_files(mg)
+    .into_iter()
+    .filter_map(|f| {
+      let path = mg.relative_to_root(f.to_str().unwrap_or_default());
+      if ignore.iter().any(|p| p.matches(&path)) {
+        return None;
+      }
+      let size = fs::metadata(&f).map(|m| m.len()).unwrap_or_default();
+      Some(UnreachableFile { path, size })
+    })
+    .collect()
+}
+
+/// Report files not reachable from configured entries
+pub fn check_unreachable(mg: &ModuleGraph) -> Vec<Diagnostic> {
+  unreachable_files(mg)
+    .into_iter()
+    .map(|f| {
+      let file = f.to_str().unwrap_or_default().to_string();
+      Diagnostic {
+        code: "unreachable-file",
+        severity: Severity::Warning,
+        message: format!(
+          "{} is not reachable from entries",
+          mg.relative_to_root(&file)
+        ),
+        file: Some(file),
+        loc: None,
+      }
+    })
+    .collect()
+}
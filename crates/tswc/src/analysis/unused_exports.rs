@@ -0,0 +1,94 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::exports::{imported_name, is_export_star, resolve_export_src, ALL};
+use super::{Diagnostic, Severity};
+use crate::compiler::{EdgeKind, ModuleGraph, ModuleKind};
+use crate::utils::ExportKind;
+
+/// Collect used (module id, export name) pairs, names used through `export *` are followed
+fn collect_used(mg: &ModuleGraph, public: &[String]) -> HashSet<(String, String)> {
+  let mut queue: Vec<(String, String)> = public
+    .iter()
+    .map(|id| (id.clone(), ALL.to_string()))
+    .collect();
+  for edge in &mg.edges {
+    match edge.kind {
+      EdgeKind::ReExport => {}
+      // Side effect import, e.g. `import './a'`
+      EdgeKind::Static | EdgeKind::StaticSourcePhase if edge.names.is_empty() => {}
+      // Names of dynamic import are unknown
+      EdgeKind::Dynamic | EdgeKind::DynamicSourcePhase if edge.names.is_empty() => {
+        queue.push((edge.to.clone(), ALL.to_string()));
+      }
+      _ => queue.extend(edge.names.iter().map(|f| (edge.to.clone(), f.clone()))),
+    }
+  }
+
+  let mut used: HashSet<(String, String)> = HashSet::new();
+  while let Some((id, name)) = queue.pop() {
+    if used.contains(&(id.clone(), ALL.to_string())) || !used.insert((id.clone(), name.clone())) {
+      continue;
+    }
+    let Some(m) = mg.modules.get(&id) else {
+      continue;
+    };
+    let is_all = name == ALL;
+    let declared = m.exports.iter().any(|f| f.n == name);
+    for export in &m.exports {
+      let Some(to) = resolve_export_src(mg, &id, export) else {
+        continue;
+      };
+      if is_export_star(export) {
+        // `default` is never re-exported by `export *`
+        if is_all || (!declared && name != "default") {
+          queue.push((to, name.clone()));
+        }
+      } else if is_all || export.n == name {
+        queue.push((to, imported_name(export).to_string()));
+      }
+    }
+  }
+  used
+}
+
+/// Report exports of project modules never imported by other modules, exports of `public` entries are used
+pub fn check_unused_exports(mg: &ModuleGraph, public: &[String]) -> Vec<Diagnostic> {
+  let used = collect_used(mg, public);
+  let mut ids: Vec<&String> = mg
+    .modules
+    .iter()
+    .filter(|(_, m)| m.used && m.kind() == ModuleKind::Script)
+    .map(|(id, _)| id)
+    .collect();
+  ids.sort();
+  let mut diagnostics = vec![];
+  for id in ids {
+    if used.contains(&(id.clone(), ALL.to_string())) {
+      continue;
+    }
+    let names: BTreeSet<&str> = mg.modules[id]
+      .exports
+      .iter()
+      // Type only imports are erased before module graph is built
+      .filter(|f| !is_export_star(f) && f.kind != ExportKind::Type)
+      .map(|f| f.n.as_str())
+      .collect();
+    for name in names {
+      if used.contains(&(id.clone(), name.to_string())) {
+        continue;
+      }
+      diagnostics.push(Diagnostic {
+        code: "unused-export",
+        severity: Severity::Warning,
+        message: format!(
+          "Export `{}` of {} is never imported",
+          name,
+          mg.relative_to_root(id)
+        ),
+        file: Some(id.clone()),
+        loc: None,
+      });
+    }
+  }
+  diagnostics
+}
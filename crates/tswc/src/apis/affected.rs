@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+
+use glob::Pattern;
+use napi_derive::napi;
+use sugar_path::SugarPath;
+
+use super::transform::build_module_graph;
+use super::TransformOptions;
+use crate::compiler::ModuleKind;
+
+#[napi(object)]
+pub struct AffectedOptions {
+  /// Changed files, relative to root or absolute
+  pub changed: Vec<String>,
+  /// Globs relative to root, only matched files are returned, e.g. `**/*.test.ts`
+  pub include: Option<Vec<String>>,
+}
+
+/// Project modules depend on changed files directly or indirectly, changed files are included.
+/// Returned paths are relative to root, nothing is written into output dir
+pub fn affected(
+  options: TransformOptions,
+  affected_options: AffectedOptions,
+) -> napi::Result<Vec<String>> {
+  let mg = build_module_graph(&options, false, None)?;
+  let include: Vec<Pattern> = affected_options
+    .include
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|f| Pattern::new(f).ok())
+    .collect();
+  let mut affected = BTreeSet::new();
+  for changed in affected_options.changed {
+    let path = mg.config.options.root.join(changed).normalize();
+    let id = path.to_str().unwrap_or_default();
+    if !mg.modules.contains_key(id) {
+      continue;
+    }
+    affected.insert(id.to_string());
+    affected.extend(mg.transitive_dependents(id));
+  }
+  Ok(
+    affected
+      .into_iter()
+      .filter(|id| {
+        mg.modules
+          .get(id)
+          .is_some_and(|m| m.kind() == ModuleKind::Script)
+      })
+      .map(|id| mg.relative_to_root(&id))
+      .filter(|path| include.is_empty() || include.iter().any(|p| p.matches(path)))
+      .collect(),
+  )
+}
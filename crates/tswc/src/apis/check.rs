@@ -0,0 +1,18 @@
+use super::transform::{build_module_graph, check_api, circular_options, collect_diagnostics};
+use super::{TransformOptions, TransformResult};
+use crate::analysis::check_imports;
+
+/// Verify imported names are exported by project modules, along with configured checks.
+/// Nothing is written into output dir
+pub fn check(options: TransformOptions) -> napi::Result<TransformResult> {
+  let circular = circular_options(&options)?;
+  let mg = build_module_graph(&options, false, None)?;
+  let mut diagnostics = check_imports(&mg);
+  diagnostics.extend(collect_diagnostics(&mg, &options, circular.as_ref()));
+  if let Some(api) = &options.api {
+    diagnostics.extend(check_api(&mg, api)?);
+  }
+  Ok(TransformResult {
+    diagnostics: diagnostics.into_iter().map(|f| f.into()).collect(),
+  })
+}
@@ -1,3 +1,18 @@
+mod affected;
+mod check;
+mod parse;
 mod transform;
+mod unreachable;
+mod unused_exports;
 
-pub use transform::{transform, TransformOptions};
+pub use affected::{affected, AffectedOptions};
+pub use check::check;
+pub use parse::{parse, ParseExport, ParseImport, ParseResult};
+pub use transform::{
+  transform, TransformApiOptions, TransformBoundaryRule, TransformCircularOptions,
+  TransformDependenciesOptions, TransformDiagnostic, TransformGraphOptions,
+  TransformModularizeImportOptions, TransformOptimizeOptions, TransformOptions, TransformResult,
+  TransformWorkspacePackageOptions,
+};
+pub use unreachable::{unreachable, UnreachableFileResult, UnreachableOptions};
+pub use unused_exports::unused_exports;
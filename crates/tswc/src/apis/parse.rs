@@ -0,0 +1,183 @@
+use napi_derive::napi;
+use swc_core::common::{BytePos, Span};
+
+use crate::compiler::{parse as parse_module, ParsedModule};
+use crate::utils::{ExportKind, ImportType};
+
+#[napi(object)]
+pub struct ParseImport {
+  /// Module specifier, none for dynamic import of non string literal and `import.meta`
+  pub n: Option<String>,
+  /// `1` static, `2` dynamic, `3` import.meta, `4` static source phase, `5` dynamic source phase
+  pub t: u32,
+  /// Start of module specifier
+  pub s: i32,
+  /// End of module specifier
+  pub e: i32,
+  /// Start of import statement
+  pub ss: i32,
+  /// End of import statement
+  pub se: i32,
+  /// Start of dynamic import bracket, `-1` for static import, `-2` for import.meta
+  pub d: i32,
+  /// Start of import attributes, `-1` if there are none
+  pub a: i32,
+}
+
+#[napi(object)]
+pub struct ParseExport {
+  /// Exported name
+  pub n: String,
+  /// Local name, none for re-exports
+  pub ln: Option<String>,
+  /// Start of exported name
+  pub s: i32,
+  /// End of exported name
+  pub e: i32,
+  /// Start of local name, `-1` if there is none
+  pub ls: i32,
+  /// End of local name, `-1` if there is none
+  pub le: i32,
+}
+
+#[napi(object)]
+pub struct ParseResult {
+  pub imports: Vec<ParseImport>,
+  pub exports: Vec<ParseExport>,
+  /// Module only contains imports and re-exports
+  pub facade: bool,
+  pub has_module_syntax: bool,
+}
+
+/// Offsets in UTF-16 code units like es-module-lexer does
+struct Offsets<'a> {
+  src: &'a str,
+  start: BytePos,
+  is_ascii: bool,
+}
+
+impl<'a> Offsets<'a> {
+  fn of(&self, pos: BytePos) -> i32 {
+    let index = (pos.0 - self.start.0) as usize;
+    if self.is_ascii {
+      return index as i32;
+    }
+    self.src[..index].encode_utf16().count() as i32
+  }
+  fn text(&self, span: Span) -> String {
+    let lo = (span.lo.0 - self.start.0) as usize;
+    let hi = (span.hi.0 - self.start.0) as usize;
+    self.src[lo..hi].to_string()
+  }
+}
+
+/// Parse imports and exports of TypeScript or JavaScript code, same shape as es-module-lexer
+pub fn parse(code: String, filename: String) -> napi::Result<ParseResult> {
+  let ParsedModule {
+    fm,
+    imports,
+    exports,
+    facade,
+    has_module_syntax,
+  } = parse_module(code, &filename).map_err(napi::Error::from_reason)?;
+  let offsets = Offsets {
+    src: &fm.src,
+    start: fm.start_pos,
+    is_ascii: fm.src.is_ascii(),
+  };
+  let imports = imports
+    .into_iter()
+    .map(|import| {
+      let is_str = import.src.is_some();
+      let (s, e) = match import.src_span {
+        // Quotes are excluded
+        Some(span) if is_str => (offsets.of(span.lo) + 1, offsets.of(span.hi) - 1),
+        Some(span) => (offsets.of(span.lo), offsets.of(span.hi)),
+        None => (
+          offsets.of(import.statement_span.lo),
+          offsets.of(import.statement_span.hi),
+        ),
+      };
+      let d = match (import.t, import.dynamic) {
+        (ImportType::ImportMeta, _) => -2,
+        (_, Some(pos)) => offsets.of(pos),
+        (_, None) => -1,
+      };
+      ParseImport {
+        n: import.src,
+        t: import.t as u32,
+        s,
+        e,
+        ss: offsets.of(import.statement_span.lo),
+        se: offsets.of(import.statement_span.hi),
+        d,
+        a: import.attributes.map(|f| offsets.of(f.lo)).unwrap_or(-1),
+      }
+    })
+    .collect();
+  let exports = exports
+    .into_iter()
+    // `export * from 'b'` is reported as import only, type exports are erased
+    .filter(|export| export.n != "*" && export.kind != ExportKind::Type)
+    .map(|export| {
+      let (s, e) = export
+        .span
+        .map(|f| (offsets.of(f.lo), offsets.of(f.hi)))
+        .unwrap_or((-1, -1));
+      let (ls, le) = export
+        .local_span
+        .map(|f| (offsets.of(f.lo), offsets.of(f.hi)))
+        .unwrap_or((-1, -1));
+      ParseExport {
+        n: export.n,
+        ln: export.local_span.map(|f| offsets.text(f)),
+        s,
+        e,
+        ls,
+        le,
+      }
+    })
+    .collect();
+  Ok(ParseResult {
+    imports,
+    exports,
+    facade,
+    has_module_syntax,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// UTF-16 offset of first occurrence of `needle`
+  fn utf16_index(code: &str, needle: &str) -> i32 {
+    let index = code.find(needle).unwrap();
+    code[..index].encode_utf16().count() as i32
+  }
+
+  #[test]
+  fn utf16_offsets_of_non_ascii_source() {
+    let code =
+      "// 日本語 😀\nimport a from 'pkg';\nconst b = import('./lazy');\nexport const ü = a;\n";
+    let result = parse(code.to_string(), "index.ts".to_string()).unwrap();
+
+    let static_import = &result.imports[0];
+    assert_eq!(static_import.n.as_deref(), Some("pkg"));
+    assert_eq!(static_import.s, utf16_index(code, "pkg"));
+    assert_eq!(static_import.e, utf16_index(code, "pkg") + 3);
+    assert_eq!(static_import.ss, utf16_index(code, "import a"));
+    // Byte offsets differ, the comment takes 17 bytes but 9 code units
+    assert_ne!(static_import.ss as usize, code.find("import a").unwrap());
+
+    let dynamic_import = &result.imports[1];
+    assert_eq!(dynamic_import.n.as_deref(), Some("./lazy"));
+    assert_eq!(dynamic_import.s, utf16_index(code, "./lazy"));
+    assert_eq!(dynamic_import.d, utf16_index(code, "('./lazy')"));
+
+    let export = &result.exports[0];
+    assert_eq!(export.n, "ü");
+    assert_eq!(export.s, utf16_index(code, "ü"));
+    assert_eq!(export.e, utf16_index(code, "ü") + 1);
+  }
+}
@@ -1,6 +1,20 @@
-use crate::compiler::{compile, optimize, Assets, ModuleGraph, ResolveModuleOptions};
-use crate::config::{Config, ConfigOptions};
-use crate::resolver::{Format, Resolver, ResolverOptions};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::{
+  check_api_surface, check_circular, check_dependencies, check_duplicates, check_unreachable,
+  ApiSurface, CircularOptions, DependenciesOptions, Diagnostic, Severity,
+};
+use crate::compiler::{
+  compile, Assets, GraphExportOptions, GraphFormat, Manifest, ModuleGraph, ResolveModuleOptions,
+};
+use crate::config::{
+  BarrelSideEffects, BoundaryRule, Config, ConfigOptions, ModularizeImport, WorkspaceStrategy,
+};
+use crate::plugins::validate_template;
+use crate::resolver::{Format, PackageJson, Resolver, ResolverOptions};
+use crate::utils::{package_name_of_path, BARREL_PRESETS, TEST_GLOBS};
 use log::debug;
 use napi_derive::napi;
 use sugar_path::SugarPath;
@@ -9,55 +23,434 @@ pub struct PreOptimizeOptions<'a> {
   pub root: String,
   /// Pre optimized packages
   pub barrel_packages: Vec<String>,
+  /// Also optimize dependencies declare `sideEffects: false`
+  pub side_effects_free: bool,
+  pub side_effects: BarrelSideEffects,
   pub mg: &'a mut ModuleGraph,
 }
 
+/// package.json of package `name` installed in node_modules of root or its ancestors
+fn installed_package(root: &Path, name: &str) -> Option<PackageJson> {
+  root
+    .ancestors()
+    .find_map(|dir| PackageJson::read(&dir.join("node_modules").join(name)))
+}
+
+/// Dependencies of root package installed with `sideEffects: false`
+fn side_effects_free_packages(root: &Path) -> Vec<String> {
+  let Some(pkg) = PackageJson::read(root) else {
+    return vec![];
+  };
+  pkg
+    .dependencies("dependencies")
+    .into_iter()
+    .filter(|name| installed_package(root, name).is_some_and(|f| f.is_side_effects_free()))
+    .collect()
+}
+
+/// Entry of `barrel_packages` expanded to installed packages of presets
+const PRESETS_PLACEHOLDER: &str = "...";
+
+/// Installed packages of presets
+fn installed_presets(root: &Path) -> Vec<String> {
+  BARREL_PRESETS
+    .iter()
+    .filter(|name| installed_package(root, name).is_some())
+    .map(|name| name.to_string())
+    .collect()
+}
+
+/// `barrel_packages` replaces presets, unless it contains `...`. Presets are used only if enabled
+fn resolve_barrel_packages(root: &Path, options: &TransformOptimizeOptions) -> Vec<String> {
+  let presets = || {
+    if options.presets.unwrap_or(false) {
+      installed_presets(root)
+    } else {
+      vec![]
+    }
+  };
+  let Some(barrel_packages) = &options.barrel_packages else {
+    return presets();
+  };
+  let mut packages: Vec<String> = vec![];
+  for package in barrel_packages {
+    let expanded = if package == PRESETS_PLACEHOLDER {
+      presets()
+    } else {
+      vec![package.clone()]
+    };
+    for package in expanded {
+      if !packages.contains(&package) {
+        packages.push(package);
+      }
+    }
+  }
+  packages
+}
+
+/// Report barrel packages whose modules resolved by optimization may have side effects, the
+/// evaluation of them is skipped once imports are rewritten. Returns packages to optimize
+fn check_side_effects(
+  mg: &mut ModuleGraph,
+  barrel_packages: Vec<String>,
+  mode: BarrelSideEffects,
+) -> Vec<String> {
+  if mode == BarrelSideEffects::Ignore {
+    return barrel_packages;
+  }
+  let mut packages = vec![];
+  for package in barrel_packages {
+    let mut files: Vec<&str> = mg
+      .modules
+      .values()
+      .filter(|m| m.is_node_modules)
+      .filter(|m| package_name_of_path(&m.abs_path).as_deref() == Some(package.as_str()))
+      .map(|m| m.abs_path.as_str())
+      .collect();
+    files.sort();
+    let pkg = files.first().and_then(|f| PackageJson::read_installed(f));
+    let Some(pkg) = pkg else {
+      packages.push(package);
+      continue;
+    };
+    let impure: Vec<String> = files
+      .into_iter()
+      .filter(|f| pkg.has_side_effects(f.as_path()))
+      .map(|f| mg.relative_to_root(f))
+      .collect();
+    let Some(first) = impure.first() else {
+      packages.push(package);
+      continue;
+    };
+    let mut modules = first.clone();
+    if impure.len() > 1 {
+      modules = format!("{} and {} more", modules, impure.len() - 1);
+    }
+    let action = match mode {
+      BarrelSideEffects::Skip => "the package is not optimized",
+      _ => {
+        packages.push(package.clone());
+        "their evaluation may be skipped by barrel optimization"
+      }
+    };
+    let manifest = pkg.root.join("package.json");
+    mg.diagnostics.push(Diagnostic {
+      code: "barrel-side-effects",
+      severity: Severity::Warning,
+      message: format!(
+        "{} of `{}` may have side effects according to `sideEffects`, {}",
+        modules, package, action
+      ),
+      file: Some(manifest.to_str().unwrap_or_default().to_string()),
+      loc: None,
+    });
+  }
+  packages
+}
+
 pub fn pre_optimize(options: PreOptimizeOptions) {
   let PreOptimizeOptions {
     root,
-    barrel_packages,
-    mut mg,
+    mut barrel_packages,
+    side_effects_free,
+    side_effects,
+    mg,
   } = options;
-  for package in barrel_packages {
+  if side_effects_free {
+    for package in side_effects_free_packages(&mg.config.options.root) {
+      if !barrel_packages.contains(&package) {
+        debug!(target: "tswc", "optimize side effects free package {:?}", package);
+        barrel_packages.push(package);
+      }
+    }
+  }
+  for package in &barrel_packages {
     mg.resolve_module(ResolveModuleOptions {
-      src: Some(package),
+      src: Some(package.clone()),
       context: root.clone(),
       is_wildcard: Some(true),
       format: Some(Format::ESM),
       ..Default::default()
     });
   }
-  while mg.get_wildcard_modules_size() != 0 {
-    let paths_to_compile: Vec<_> = {
-      let unused_modules = mg.get_wildcard_modules();
-      unused_modules
-        .map(|decl| {
-          decl.optimized = true;
-          debug!(
-              target: "tswc",
-              "optimize! {:?}", &decl.abs_path
-          );
-          (decl.abs_path.clone(), decl.is_script, decl.is_wildcard)
-        })
-        .collect()
-    };
-    for (resolved_path, is_script, is_wildcard) in paths_to_compile {
-      if is_script {
-        optimize(&resolved_path, &mut mg, Some(is_wildcard));
-      } else {
-      }
-    }
-  }
+  mg.optimize_wildcard_modules();
+  mg.config.resolved_options.barrel_packages =
+    check_side_effects(mg, barrel_packages, side_effects);
   debug!("Finish pre optimized")
 }
 
 #[napi(object)]
+#[derive(Default)]
 pub struct TransformOptimizeOptions {
-  /// Optimized packages
+  /// Optimized packages, replaces presets. `...` is expanded to presets e.g. `['...', 'my-ui']`
   pub barrel_packages: Option<Vec<String>>,
+  /// Optimize installed packages of built-in list e.g. `@mui/material` and `lodash-es`, default is
+  /// false
+  pub presets: Option<bool>,
+  /// Rewrite imports through pure barrel files of project to underlying modules, default is false
+  pub local_barrels: Option<bool>,
+  /// Globs relative to root, matched files are never optimized as local barrels
+  pub exclude_barrels: Option<Vec<String>>,
+  /// Optimize dependencies declare `sideEffects: false` in their package.json, default is false
+  pub side_effects_free: Option<bool>,
+  /// Barrel packages may have side effects skipped by optimization
+  /// `warn` (default) optimize and report a warning
+  /// `skip` do not optimize and report a warning
+  /// `ignore` optimize silently
+  pub side_effects: Option<String>,
+  /// Rewrite named imports of packages by templates without resolving them, keyed by package name
+  pub modularize_imports: Option<HashMap<String, TransformModularizeImportOptions>>,
+}
+
+#[napi(object)]
+pub struct TransformModularizeImportOptions {
+  /// Template of module path e.g. `lodash/{{member}}`, helpers `camelCase`, `kebabCase`,
+  /// `snakeCase`, `upperCase` and `lowerCase` are supported e.g. `@icons/{{kebabCase member}}`
+  pub transform: String,
+  /// Keep named imports, otherwise they are converted to default imports
+  pub skip_default_conversion: Option<bool>,
+  /// Templates of specific members, keyed by imported name
+  pub members: Option<HashMap<String, String>>,
+}
+
+impl TryFrom<&TransformModularizeImportOptions> for ModularizeImport {
+  type Error = napi::Error;
+  fn try_from(options: &TransformModularizeImportOptions) -> napi::Result<Self> {
+    let members = options.members.clone().unwrap_or_default();
+    for template in std::iter::once(&options.transform).chain(members.values()) {
+      validate_template(template).map_err(napi::Error::from_reason)?;
+    }
+    Ok(ModularizeImport {
+      transform: options.transform.clone(),
+      skip_default_conversion: options.skip_default_conversion.unwrap_or(false),
+      members,
+    })
+  }
+}
+
+impl TryFrom<&TransformOptimizeOptions> for BarrelSideEffects {
+  type Error = napi::Error;
+  fn try_from(options: &TransformOptimizeOptions) -> napi::Result<Self> {
+    match options.side_effects.as_deref() {
+      None | Some("warn") => Ok(BarrelSideEffects::Warn),
+      Some("skip") => Ok(BarrelSideEffects::Skip),
+      Some("ignore") => Ok(BarrelSideEffects::Ignore),
+      Some(other) => Err(napi::Error::from_reason(format!(
+        "Unknown barrel side effects mode {:?}",
+        other
+      ))),
+    }
+  }
+}
+
+#[napi(object)]
+pub struct TransformWorkspacePackageOptions {
+  /// `inline` (default) compile sources into output dir
+  /// `external` keep the bare specifier
+  /// `output` rewrite to built output declared by its package.json `main`/`exports`
+  pub strategy: Option<String>,
+  /// Destination of inlined sources relative to output dir, only works with `inline` strategy
+  pub dest: Option<String>,
+}
+
+impl TryFrom<&TransformWorkspacePackageOptions> for WorkspaceStrategy {
+  type Error = napi::Error;
+  fn try_from(options: &TransformWorkspacePackageOptions) -> napi::Result<Self> {
+    let TransformWorkspacePackageOptions { strategy, dest } = options;
+    match strategy.as_deref() {
+      None | Some("inline") => Ok(WorkspaceStrategy::Inline { dest: dest.clone() }),
+      Some("external") => Ok(WorkspaceStrategy::External),
+      Some("output") => Ok(WorkspaceStrategy::Output),
+      Some(other) => Err(napi::Error::from_reason(format!(
+        "Unknown workspace package strategy {:?}",
+        other
+      ))),
+    }
+  }
+}
+
+#[napi(object)]
+pub struct TransformGraphOptions {
+  /// `json` (default) or `dot`
+  pub format: Option<String>,
+  /// Output filepath, default is `tswc-graph.json` or `tswc-graph.dot` in output dir
+  pub path: Option<String>,
+  /// Collapse modules of the same node_modules package into single node
+  pub collapse_packages: Option<bool>,
+  /// Only export modules reachable from root module, relative to root
+  pub root: Option<String>,
+  /// Max depth from root module
+  pub depth: Option<u32>,
+}
+
+impl TryFrom<&TransformGraphOptions> for GraphExportOptions {
+  type Error = napi::Error;
+  fn try_from(options: &TransformGraphOptions) -> napi::Result<Self> {
+    let format = match options.format.as_deref() {
+      None | Some("json") => GraphFormat::Json,
+      Some("dot") => GraphFormat::Dot,
+      Some(other) => {
+        return Err(napi::Error::from_reason(format!(
+          "Unknown graph format {:?}",
+          other
+        )))
+      }
+    };
+    Ok(GraphExportOptions {
+      format,
+      collapse_packages: options.collapse_packages.unwrap_or(false),
+      root: options.root.clone(),
+      depth: options.depth.map(|f| f as usize),
+    })
+  }
+}
+
+#[napi(object)]
+pub struct TransformCircularOptions {
+  /// Globs relative to root, cycle is ignored if every module in it matches one of them
+  pub allowlist: Option<Vec<String>>,
+  /// `warning` (default) or `error`
+  pub severity: Option<String>,
+}
+
+fn parse_severity(severity: &Option<String>) -> napi::Result<Severity> {
+  match severity.as_deref() {
+    None | Some("warning") => Ok(Severity::Warning),
+    Some("error") => Ok(Severity::Error),
+    Some(other) => Err(napi::Error::from_reason(format!(
+      "Unknown severity {:?}",
+      other
+    ))),
+  }
+}
+
+impl TryFrom<&TransformCircularOptions> for CircularOptions {
+  type Error = napi::Error;
+  fn try_from(options: &TransformCircularOptions) -> napi::Result<Self> {
+    Ok(CircularOptions {
+      allowlist: options.allowlist.clone().unwrap_or_default(),
+      severity: parse_severity(&options.severity)?,
+    })
+  }
 }
 
 #[napi(object)]
+pub struct TransformDependenciesOptions {
+  /// Globs relative to root, devDependencies are allowed in matched files, default is test files e.g. `**/*.test.*`
+  pub test: Option<Vec<String>>,
+}
+
+impl From<&TransformDependenciesOptions> for DependenciesOptions {
+  fn from(options: &TransformDependenciesOptions) -> Self {
+    DependenciesOptions {
+      test: options
+        .test
+        .clone()
+        .unwrap_or_else(|| TEST_GLOBS.iter().map(|f| f.to_string()).collect()),
+    }
+  }
+}
+
+#[napi(object)]
+pub struct TransformBoundaryRule {
+  /// Globs of importers relative to root, every module by default
+  pub from: Option<Vec<String>>,
+  /// Globs of targets relative to root, or package names e.g. `pg`
+  pub to: Vec<String>,
+  /// Globs of importers relative to root allowed to import targets
+  pub except: Option<Vec<String>>,
+  /// Reason appended to diagnostic message
+  pub message: Option<String>,
+  /// `error` (default) or `warning`
+  pub severity: Option<String>,
+}
+
+impl TryFrom<&TransformBoundaryRule> for BoundaryRule {
+  type Error = napi::Error;
+  fn try_from(rule: &TransformBoundaryRule) -> napi::Result<Self> {
+    Ok(BoundaryRule {
+      from: rule.from.clone().unwrap_or_default(),
+      to: rule.to.clone(),
+      except: rule.except.clone().unwrap_or_default(),
+      message: rule.message.clone(),
+      severity: match rule.severity.as_deref() {
+        None => Severity::Error,
+        _ => parse_severity(&rule.severity)?,
+      },
+    })
+  }
+}
+
+#[napi(object)]
+pub struct TransformApiOptions {
+  /// Baseline filepath relative to root or absolute, default is `tswc-api.json` in root
+  pub baseline: Option<String>,
+  /// Overwrite baseline with current API surface instead of comparing
+  pub update: Option<bool>,
+  /// Throw an error if API surface is broken, default is true. Set false to only report diagnostics
+  pub fail_on_breaking: Option<bool>,
+}
+
+impl TransformApiOptions {
+  fn baseline_path(&self, mg: &ModuleGraph) -> PathBuf {
+    let baseline = self.baseline.as_deref().unwrap_or("tswc-api.json");
+    mg.config.options.root.join(baseline).normalize()
+  }
+}
+
+/// API surface of public entries, configured `entries` or sources of package.json targets
+pub(crate) fn api_surface(mg: &ModuleGraph) -> ApiSurface {
+  let entries: Vec<String> = mg
+    .config
+    .public_entries()
+    .iter()
+    .map(|f| f.to_str().unwrap_or_default().to_string())
+    .collect();
+  ApiSurface::new(mg, &entries)
+}
+
+/// Compare API surface with baseline, a warning is reported if baseline does not exist.
+/// Breaking changes are returned as error unless `fail_on_breaking` is false
+pub(crate) fn check_api(
+  mg: &ModuleGraph,
+  options: &TransformApiOptions,
+) -> napi::Result<Vec<Diagnostic>> {
+  let path = options.baseline_path(mg);
+  let file = Some(path.to_str().unwrap_or_default().to_string());
+  let Ok(content) = fs::read_to_string(&path) else {
+    return Ok(vec![Diagnostic {
+      code: "api-baseline-missing",
+      severity: Severity::Warning,
+      message: format!(
+        "API baseline {} does not exist, run transform to create it",
+        mg.relative_to_root(file.as_deref().unwrap_or_default())
+      ),
+      file,
+      loc: None,
+    }]);
+  };
+  let diagnostics = match ApiSurface::from_json(&content) {
+    Ok(baseline) => check_api_surface(&mg.config.options.root, &baseline, &api_surface(mg)),
+    Err(err) => vec![Diagnostic {
+      code: "api-baseline-invalid",
+      severity: Severity::Error,
+      message: format!("Failed to parse API baseline: {}", err),
+      file,
+      loc: None,
+    }],
+  };
+  if options.fail_on_breaking.unwrap_or(true) && !diagnostics.is_empty() {
+    let messages: Vec<&str> = diagnostics.iter().map(|f| f.message.as_str()).collect();
+    return Err(napi::Error::from_reason(format!(
+      "Breaking API changes:\n{}",
+      messages.join("\n")
+    )));
+  }
+  Ok(diagnostics)
+}
+
+#[napi(object)]
+#[derive(Default)]
 pub struct TransformOptions {
   pub root: String,
   // override tsconfig outDir
@@ -65,52 +458,140 @@ pub struct TransformOptions {
   pub externals: Option<Vec<String>>,
   // override tsconfig exclude
   pub exclude: Option<Vec<String>>,
+  /// Entry files relative to root, only files reachable from them are compiled
+  pub entries: Option<Vec<String>>,
   // TODO: should nested in resolve config
   pub modules: Option<Vec<String>>,
+  /// Write `tswc-manifest.json` into output dir after build
+  pub manifest: Option<bool>,
+  /// Write module graph into output dir after build
+  pub graph: Option<TransformGraphOptions>,
+  /// Report import cycles between project modules
+  pub circular: Option<TransformCircularOptions>,
+  /// Report undeclared, misplaced and unused package.json dependencies
+  pub dependencies: Option<TransformDependenciesOptions>,
+  /// Report packages resolved to several copies in node_modules
+  pub duplicates: Option<bool>,
+  /// Module boundary rules checked against every import, e.g. `src/ui/**` may not import `src/server/**`
+  pub rules: Option<Vec<TransformBoundaryRule>>,
+  /// Compare exports of public entries with baseline, removed or changed exports are errors
+  pub api: Option<TransformApiOptions>,
+  /// Strategies of `paths` mapped workspace packages, keyed by package name
+  pub workspace_packages: Option<HashMap<String, TransformWorkspacePackageOptions>>,
   /// Optimized options
   pub optimize: TransformOptimizeOptions,
 }
 
-pub fn transform(options: TransformOptions) {
-  env_logger::init();
-  let assets = Assets::new();
+#[napi(object)]
+pub struct TransformDiagnostic {
+  pub code: String,
+  /// `warning` or `error`
+  pub severity: String,
+  pub message: String,
+  pub file: Option<String>,
+  pub line: Option<u32>,
+  pub column: Option<u32>,
+}
+
+impl From<Diagnostic> for TransformDiagnostic {
+  fn from(diagnostic: Diagnostic) -> Self {
+    TransformDiagnostic {
+      code: diagnostic.code.to_string(),
+      severity: diagnostic.severity.as_str().to_string(),
+      message: diagnostic.message,
+      file: diagnostic.file,
+      line: diagnostic.loc.map(|f| f.line as u32),
+      column: diagnostic.loc.map(|f| f.column as u32),
+    }
+  }
+}
+
+#[napi(object)]
+pub struct TransformResult {
+  pub diagnostics: Vec<TransformDiagnostic>,
+}
+
+/// Resolve config and build module graph from entries, compiled modules are written only if `assets` is provided
+pub(crate) fn build_module_graph(
+  options: &TransformOptions,
+  package_entries: bool,
+  assets: Option<&Assets>,
+) -> napi::Result<ModuleGraph> {
+  let _ = env_logger::try_init();
   let TransformOptions {
     root,
     output,
     externals,
     exclude,
+    entries,
     modules,
+    workspace_packages,
+    rules,
     optimize,
+    ..
   } = options;
-  let TransformOptimizeOptions { barrel_packages } = optimize;
   let root_cloned = root.clone();
   let root = root.as_path().absolutize();
+  let barrel_packages = resolve_barrel_packages(&root, optimize);
   let tsconfig_path = root.join("tsconfig.json");
   let resolver = Resolver::new(ResolverOptions {
-    externals: externals.unwrap_or(vec![]),
-    modules: modules.unwrap_or(vec!["node_modules".into()]),
+    externals: externals.clone().unwrap_or(vec![]),
+    modules: modules.clone().unwrap_or(vec!["node_modules".into()]),
     tsconfig: tsconfig_path.clone(),
   });
   debug!(target: "tswc", "root {:?}", root);
+  let side_effects = BarrelSideEffects::try_from(optimize)?;
   let config_options = ConfigOptions {
     root,
-    output,
-    exclude,
-    barrel_packages: barrel_packages.clone().unwrap_or_default(),
+    output: output.clone(),
+    exclude: exclude.clone(),
+    entries: entries.clone(),
+    package_entries,
+    barrel_packages: barrel_packages.clone(),
+    local_barrels: optimize.local_barrels.unwrap_or(false),
+    exclude_barrels: optimize.exclude_barrels.clone().unwrap_or_default(),
+    modularize_imports: optimize
+      .modularize_imports
+      .iter()
+      .flatten()
+      .map(|(name, options)| Ok((name.clone(), options.try_into()?)))
+      .collect::<napi::Result<_>>()?,
+    workspace_packages: workspace_packages
+      .iter()
+      .flatten()
+      .map(|(name, options)| Ok((name.clone(), options.try_into()?)))
+      .collect::<napi::Result<_>>()?,
+    rules: rules
+      .iter()
+      .flatten()
+      .map(BoundaryRule::try_from)
+      .collect::<napi::Result<_>>()?,
   };
   let mut config = Config::new(config_options);
   config.resolve_options(&tsconfig_path);
   config.search_files();
-  let files = config.files.clone();
+  let files = config
+    .resolved_options
+    .entries
+    .clone()
+    .unwrap_or_else(|| config.files.clone());
   let mut mg = ModuleGraph::new(resolver, config);
   debug!(target: "tswc", "files {:?}", files);
   pre_optimize(PreOptimizeOptions {
     root: root_cloned,
-    barrel_packages: barrel_packages.unwrap_or_default(),
+    barrel_packages,
+    side_effects_free: optimize.side_effects_free.unwrap_or(false),
+    side_effects,
     mg: &mut mg,
   });
   for path in files {
     let resource_path = path.as_path().absolutize();
+    if !resource_path.exists() {
+      return Err(napi::Error::from_reason(format!(
+        "Entry {} not found",
+        resource_path.display()
+      )));
+    }
     mg.resolve_entry_module(
       Some(resource_path.to_str().unwrap_or_default().to_string()),
       Some(false),
@@ -123,6 +604,7 @@ pub fn transform(options: TransformOptions) {
       unused_modules
         .map(|decl| {
           decl.used = true;
+          decl.emitted = assets.is_some();
           debug!(
               target: "tswc",
               "compile! {:?} {:?}", &decl.abs_path, &decl.v_abs_path
@@ -139,10 +621,176 @@ pub fn transform(options: TransformOptions) {
       debug!(target: "tswc", "output {} {}", output_path, is_script);
       if is_script {
         let result = compile(&resolved_path, &mut mg);
-        assets.output(&output_path, result)
-      } else {
+        if let Some(assets) = assets {
+          assets.output(&output_path, result)
+        }
+      } else if let Some(assets) = assets {
         assets.copy(&output_path, &resolved_path)
       }
     }
   }
+  Ok(mg)
+}
+
+/// Circular check options, validated before the module graph is built
+pub(crate) fn circular_options(
+  options: &TransformOptions,
+) -> napi::Result<Option<CircularOptions>> {
+  options
+    .circular
+    .as_ref()
+    .map(CircularOptions::try_from)
+    .transpose()
+}
+
+/// Diagnostics of configured checks
+pub(crate) fn collect_diagnostics(
+  mg: &ModuleGraph,
+  options: &TransformOptions,
+  circular: Option<&CircularOptions>,
+) -> Vec<Diagnostic> {
+  let mut diagnostics: Vec<Diagnostic> = mg.diagnostics.clone();
+  if mg.config.resolved_options.entries.is_some() {
+    diagnostics.extend(check_unreachable(mg));
+  }
+  if let Some(circular) = circular {
+    diagnostics.extend(check_circular(mg, circular));
+  }
+  if let Some(dependencies) = &options.dependencies {
+    diagnostics.extend(check_dependencies(mg, &dependencies.into()));
+  }
+  if options.duplicates.unwrap_or(false) {
+    diagnostics.extend(check_duplicates(mg));
+  }
+  diagnostics
+}
+
+pub fn transform(options: TransformOptions) -> napi::Result<TransformResult> {
+  let graph_options = options
+    .graph
+    .as_ref()
+    .map(GraphExportOptions::try_from)
+    .transpose()?;
+  let circular = circular_options(&options)?;
+  let assets = Assets::new();
+  let mg = build_module_graph(&options, false, Some(&assets))?;
+  if options.manifest.unwrap_or(false) {
+    let path = mg.config.resolved_options.output.join("tswc-manifest.json");
+    assets.write(&path, Manifest::new(&mg).to_json());
+  }
+  if let (Some(graph), Some(options)) = (&options.graph, graph_options) {
+    let path = match &graph.path {
+      Some(path) => path.as_path().absolutize(),
+      None => {
+        let ext = match options.format {
+          GraphFormat::Json => "json",
+          GraphFormat::Dot => "dot",
+        };
+        let name = format!("tswc-graph.{}", ext);
+        mg.config.resolved_options.output.join(name)
+      }
+    };
+    assets.write(&path, mg.export(&options));
+  }
+  let mut diagnostics = collect_diagnostics(&mg, &options, circular.as_ref());
+  if let Some(api) = &options.api {
+    let path = api.baseline_path(&mg);
+    let update = api.update.unwrap_or(false);
+    let created = !path.exists();
+    if update || created {
+      assets.write(&path, api_surface(&mg).to_json());
+    }
+    if created {
+      let file = path.to_str().unwrap_or_default().to_string();
+      diagnostics.push(Diagnostic {
+        code: "api-baseline-created",
+        severity: Severity::Warning,
+        message: format!(
+          "API baseline is created at {}, commit it to detect breaking changes",
+          mg.relative_to_root(&file)
+        ),
+        file: Some(file),
+        loc: None,
+      });
+    } else if !update {
+      diagnostics.extend(check_api(&mg, api)?);
+    }
+  }
+  Ok(TransformResult {
+    diagnostics: diagnostics.into_iter().map(|f| f.into()).collect(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use tempfile::TempDir;
+
+  use super::*;
+
+  /// Root with `@mui/material` installed
+  fn root() -> TempDir {
+    let root = TempDir::new().unwrap();
+    let package = root.path().join("node_modules/@mui/material");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(package.join("package.json"), r#"{"name":"@mui/material"}"#).unwrap();
+    root
+  }
+
+  fn resolve(presets: Option<bool>, barrel_packages: Option<&[&str]>) -> Vec<String> {
+    let options = TransformOptimizeOptions {
+      presets,
+      barrel_packages: barrel_packages.map(|f| f.iter().map(|f| f.to_string()).collect()),
+      ..Default::default()
+    };
+    resolve_barrel_packages(root().path(), &options)
+  }
+
+  #[test]
+  fn presets_are_opt_in() {
+    assert!(resolve(None, None).is_empty());
+    assert_eq!(resolve(Some(true), None), ["@mui/material"]);
+  }
+
+  #[test]
+  fn barrel_packages_replace_presets() {
+    assert_eq!(resolve(Some(true), Some(&["ui"])), ["ui"]);
+    assert_eq!(
+      resolve(Some(true), Some(&["...", "ui"])),
+      ["@mui/material", "ui"]
+    );
+    assert_eq!(resolve(None, Some(&["...", "ui"])), ["ui"]);
+  }
+
+  #[test]
+  fn unknown_side_effects_mode() {
+    let options = TransformOptimizeOptions {
+      side_effects: Some("drop".into()),
+      ..Default::default()
+    };
+    let err = BarrelSideEffects::try_from(&options).unwrap_err();
+    assert!(err.reason.contains("drop"));
+  }
+
+  #[test]
+  fn unknown_graph_format() {
+    let options = TransformGraphOptions {
+      format: Some("svg".into()),
+      path: None,
+      collapse_packages: None,
+      root: None,
+      depth: None,
+    };
+    let err = GraphExportOptions::try_from(&options).unwrap_err();
+    assert!(err.reason.contains("svg"));
+  }
+
+  #[test]
+  fn unknown_workspace_strategy() {
+    let options = TransformWorkspacePackageOptions {
+      strategy: Some("inlined".into()),
+      dest: None,
+    };
+    let err = WorkspaceStrategy::try_from(&options).unwrap_err();
+    assert!(err.reason.contains("inlined"));
+  }
 }
@@ -0,0 +1,47 @@
+use napi_derive::napi;
+
+use super::transform::build_module_graph;
+use super::TransformOptions;
+use crate::analysis::{find_unreachable, UnreachableFile};
+use crate::utils::TEST_GLOBS;
+
+#[napi(object)]
+pub struct UnreachableOptions {
+  /// Globs relative to root, default ignores test files e.g. `**/*.test.*`
+  pub ignore: Option<Vec<String>>,
+}
+
+#[napi(object)]
+pub struct UnreachableFileResult {
+  /// Filepath relative to root
+  pub path: String,
+  /// File size in bytes
+  pub size: i64,
+}
+
+impl From<UnreachableFile> for UnreachableFileResult {
+  fn from(file: UnreachableFile) -> Self {
+    UnreachableFileResult {
+      path: file.path,
+      size: file.size as i64,
+    }
+  }
+}
+
+/// List files under root not reachable from `entries`, fallback to package.json `main`/`exports` targets.
+/// Nothing is written into output dir
+pub fn unreachable(
+  options: TransformOptions,
+  unreachable_options: Option<UnreachableOptions>,
+) -> napi::Result<Vec<UnreachableFileResult>> {
+  let mg = build_module_graph(&options, true, None)?;
+  let ignore = unreachable_options
+    .and_then(|f| f.ignore)
+    .unwrap_or_else(|| TEST_GLOBS.iter().map(|f| f.to_string()).collect());
+  Ok(
+    find_unreachable(&mg, &ignore)
+      .into_iter()
+      .map(|f| f.into())
+      .collect(),
+  )
+}
@@ -0,0 +1,23 @@
+use super::transform::build_module_graph;
+use super::{TransformDiagnostic, TransformOptions};
+use crate::analysis::check_unused_exports;
+
+/// Report exports never imported by other modules, exports of `entries` are public,
+/// fallback to package.json `main`/`exports` targets. Nothing is written into output dir
+pub fn unused_exports(options: TransformOptions) -> napi::Result<Vec<TransformDiagnostic>> {
+  let mg = build_module_graph(&options, true, None)?;
+  let public: Vec<String> = mg
+    .config
+    .resolved_options
+    .entries
+    .iter()
+    .flatten()
+    .map(|f| f.to_str().unwrap_or_default().to_string())
+    .collect();
+  Ok(
+    check_unused_exports(&mg, &public)
+      .into_iter()
+      .map(|f| f.into())
+      .collect(),
+  )
+}
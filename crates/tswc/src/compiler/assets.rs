@@ -1,8 +1,11 @@
 use std::fs::{copy, create_dir_all, write};
+use std::path::Path;
 
 use sugar_path::SugarPath;
 use swc_core::base::TransformOutput;
 
+use crate::utils::output_ext;
+
 #[derive(Default, Debug)]
 pub struct Assets {}
 
@@ -11,13 +14,21 @@ impl Assets {
     Self {}
   }
   pub fn output(&self, output_path: &str, output: TransformOutput) {
-    let path = output_path.as_path().with_extension("js");
+    let path = output_path
+      .as_path()
+      .with_extension(output_ext(output_path));
     if let Some(parent) = path.parent() {
       create_dir_all(parent).expect("Failed to create directories");
     }
     let content = output.code;
     write(path, content).expect("Failed to write file");
   }
+  pub fn write(&self, path: &Path, content: String) {
+    if let Some(parent) = path.parent() {
+      create_dir_all(parent).expect("Failed to create directories");
+    }
+    write(path, content).expect("Failed to write file");
+  }
   pub fn copy(&self, output_path: &str, src: &str) {
     let path = output_path.as_path();
     if let Some(parent) = path.parent() {
@@ -606,6 +606,7 @@ impl SwcCompiler {
     &self.cm
   }
   // refs: https://github.com/web-infra-dev/rspack/blob/main/crates/rspack_plugin_javascript/src/ast/stringify.rs#L36
+  #[allow(clippy::too_many_arguments)]
   pub fn print(
     &self,
     node: &Program,
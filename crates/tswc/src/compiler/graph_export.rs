@@ -0,0 +1,196 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+use sugar_path::SugarPath;
+
+use super::{EdgeKind, Module, ModuleGraph, ModuleKind};
+use crate::utils::{package_name_of_path, package_name_of_specifier};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GraphFormat {
+  #[default]
+  Json,
+  Dot,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GraphExportOptions {
+  pub format: GraphFormat,
+  /// Collapse modules of the same node_modules package into single node
+  pub collapse_packages: bool,
+  /// Only export modules reachable from root module
+  pub root: Option<String>,
+  /// Max depth from root module, works with `root`
+  pub depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+  pub id: String,
+  /// Filepath relative to project root, or package name
+  pub label: String,
+  pub kind: ModuleKind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+  pub from: String,
+  pub to: String,
+  pub kind: EdgeKind,
+  pub specifier: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphExport {
+  pub nodes: Vec<GraphNode>,
+  pub edges: Vec<GraphEdge>,
+}
+
+fn escape_dot(value: &str) -> String {
+  value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl GraphExport {
+  pub fn new(mg: &ModuleGraph, options: &GraphExportOptions) -> GraphExport {
+    let root_dir = &mg.config.options.root;
+    let to_node = |m: &Module| -> GraphNode {
+      let package = (options.collapse_packages && m.is_node_modules)
+        .then(|| package_name_of_path(&m.abs_path).or_else(|| package_name_of_specifier(&m.src)))
+        .flatten();
+      match package {
+        Some(name) => GraphNode {
+          id: name.clone(),
+          label: name,
+          kind: ModuleKind::NodeModules,
+        },
+        None if m.abs_path.is_empty() => GraphNode {
+          id: m.src.clone(),
+          label: m.src.clone(),
+          kind: m.kind(),
+        },
+        None => GraphNode {
+          id: m.abs_path.clone(),
+          label: mg.relative_to_root(&m.abs_path),
+          kind: m.kind(),
+        },
+      }
+    };
+    let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    for m in mg.modules.values() {
+      let node = to_node(m);
+      nodes.entry(node.id.clone()).or_insert(node);
+    }
+    let mut edges: Vec<GraphEdge> = vec![];
+    for e in &mg.edges {
+      let (Some(from), Some(to)) = (mg.modules.get(&e.from), mg.modules.get(&e.to)) else {
+        continue;
+      };
+      let (from, to) = (to_node(from).id, to_node(to).id);
+      // Imports between modules of the same collapsed package
+      if from == to {
+        continue;
+      }
+      let existed = edges
+        .iter()
+        .any(|f| f.from == from && f.to == to && f.kind == e.kind && f.specifier == e.specifier);
+      if !existed {
+        edges.push(GraphEdge {
+          from,
+          to,
+          kind: e.kind,
+          specifier: e.specifier.clone(),
+        });
+      }
+    }
+    edges.sort_by(|a, b| (&a.from, &a.to, &a.specifier).cmp(&(&b.from, &b.to, &b.specifier)));
+
+    if let Some(root) = &options.root {
+      let root = root_dir.join(root).normalize();
+      let root = root.to_str().unwrap_or_default().to_string();
+      let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+      for edge in &edges {
+        adjacency
+          .entry(edge.from.as_str())
+          .or_default()
+          .push(edge.to.as_str());
+      }
+      let mut reachable: HashMap<String, usize> = HashMap::new();
+      let mut queue = VecDeque::new();
+      if nodes.contains_key(&root) {
+        reachable.insert(root.clone(), 0);
+        queue.push_back((root, 0));
+      }
+      while let Some((id, depth)) = queue.pop_front() {
+        if options.depth.is_some_and(|max| depth >= max) {
+          continue;
+        }
+        for next in adjacency.get(id.as_str()).cloned().unwrap_or_default() {
+          if !reachable.contains_key(next) {
+            reachable.insert(next.to_string(), depth + 1);
+            queue.push_back((next.to_string(), depth + 1));
+          }
+        }
+      }
+      nodes.retain(|id, _| reachable.contains_key(id));
+      edges.retain(|f| reachable.contains_key(&f.from) && reachable.contains_key(&f.to));
+    }
+
+    GraphExport {
+      nodes: nodes.into_values().collect(),
+      edges,
+    }
+  }
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).expect("Failed to serialize module graph")
+  }
+  pub fn to_dot(&self) -> String {
+    let mut lines = vec!["digraph modules {".to_string()];
+    for node in &self.nodes {
+      let shape = match node.kind {
+        ModuleKind::Script => "box",
+        ModuleKind::Asset => "note",
+        ModuleKind::NodeModules | ModuleKind::External => "component",
+        ModuleKind::Builtin | ModuleKind::NotFound => "ellipse",
+      };
+      lines.push(format!(
+        "  \"{}\" [label=\"{}\" shape={}];",
+        escape_dot(&node.id),
+        escape_dot(&node.label),
+        shape
+      ));
+    }
+    // Edges only differ in specifier, e.g. `pkg` and `pkg/lib/util` of a collapsed package, are drawn once
+    let mut drawn = HashSet::new();
+    for edge in &self.edges {
+      let style = match edge.kind {
+        EdgeKind::Dynamic | EdgeKind::DynamicSourcePhase => " [style=dashed]",
+        EdgeKind::ReExport => " [style=dotted]",
+        EdgeKind::Static | EdgeKind::StaticSourcePhase => "",
+      };
+      if drawn.insert((&edge.from, &edge.to, style)) {
+        lines.push(format!(
+          "  \"{}\" -> \"{}\"{};",
+          escape_dot(&edge.from),
+          escape_dot(&edge.to),
+          style
+        ));
+      }
+    }
+    lines.push("}".into());
+    lines.join("\n")
+  }
+}
+
+impl ModuleGraph {
+  /// Serialize modules and import edges as JSON or Graphviz DOT
+  pub fn export(&self, options: &GraphExportOptions) -> String {
+    let graph = GraphExport::new(self, options);
+    match options.format {
+      GraphFormat::Json => graph.to_json(),
+      GraphFormat::Dot => graph.to_dot(),
+    }
+  }
+}
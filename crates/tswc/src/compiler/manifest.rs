@@ -0,0 +1,73 @@
+use serde::Serialize;
+
+use super::{EdgeKind, ModuleGraph, ModuleKind};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestImport {
+  pub specifier: String,
+  pub kind: EdgeKind,
+  pub names: Vec<String>,
+  pub resolved: Option<String>,
+  pub rewritten: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestModule {
+  /// Resolved absolute filepath, specifier if it is not on file system
+  pub source: String,
+  /// Emitted filepath
+  pub output: Option<String>,
+  pub kind: ModuleKind,
+  pub entry: bool,
+  pub imports: Vec<ManifestImport>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+  pub input: String,
+  pub output: String,
+  pub modules: Vec<ManifestModule>,
+}
+
+impl Manifest {
+  pub fn new(mg: &ModuleGraph) -> Manifest {
+    let mut modules: Vec<ManifestModule> = mg
+      .modules
+      .values()
+      .map(|m| ManifestModule {
+        source: m.id(),
+        output: m.emitted.then(|| m.output_path()),
+        kind: m.kind(),
+        entry: m.is_entry,
+        imports: mg
+          .dependencies(&m.id())
+          .into_iter()
+          .map(|e| ManifestImport {
+            specifier: e.specifier.clone(),
+            kind: e.kind,
+            names: e.names.clone(),
+            resolved: mg
+              .modules
+              .get(&e.to)
+              .map(|f| f.abs_path.clone())
+              .filter(|f| !f.is_empty()),
+            rewritten: e.rewritten.clone(),
+          })
+          .collect(),
+      })
+      .collect();
+    modules.sort_by(|a, b| a.source.cmp(&b.source));
+    let options = &mg.config.resolved_options;
+    Manifest {
+      input: options.input.to_str().unwrap_or_default().to_string(),
+      output: options.output.to_str().unwrap_or_default().to_string(),
+      modules,
+    }
+  }
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).expect("Failed to serialize manifest")
+  }
+}
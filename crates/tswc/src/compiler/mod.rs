@@ -1,8 +1,17 @@
 mod assets;
+#[allow(clippy::module_inception)]
 mod compiler;
+mod graph_export;
+mod manifest;
 mod module_graph;
+mod parse;
 mod transform;
 pub use assets::Assets;
 pub use compiler::SwcCompiler;
-pub use module_graph::{ModuleGraph, ResolveModuleOptions};
+pub use graph_export::{GraphExportOptions, GraphFormat};
+pub use manifest::Manifest;
+pub use module_graph::{
+  Edge, EdgeKind, Location, Module, ModuleGraph, ModuleKind, ResolveModuleOptions,
+};
+pub use parse::{parse, ParsedModule};
 pub use transform::{compile, optimize};
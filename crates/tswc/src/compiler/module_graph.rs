@@ -1,11 +1,21 @@
 use log::debug;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use sugar_path::SugarPath;
 
-use crate::config::Config;
-use crate::resolver::{Format, Resolver};
-use crate::utils::{QUERY_RE, SCRIPT_RE};
+use glob::Pattern;
+use swc_core::base::config::ModuleConfig;
+
+use super::optimize;
+use super::transform::IntoOptions;
+use crate::analysis::{Diagnostic, Severity};
+use crate::config::{Config, WorkspaceStrategy};
+use crate::resolver::{Format, PackageJson, Resolver};
+use crate::utils::{
+  find_up_package_root, output_ext, package_name_of_path, ExportSpecifier, ImportSpecifier,
+  ImportType, QUERY_RE, SCRIPT_RE,
+};
 
 fn common_path_prefix(p1: &Path, p2: &Path) -> PathBuf {
   let mut common_prefix = PathBuf::new();
@@ -34,27 +44,27 @@ fn replace_common_prefix(p1: &Path, p2: &Path, new_prefix: &Path) -> String {
 
 fn clean_path(p: &str) -> String {
   let result = QUERY_RE.replace(p, "");
-  return result.into();
+  result.into()
 }
 
 fn get_matches(
   module: Option<&Module>,
   mg: &ModuleGraph,
   export_map: &mut HashMap<String, (String, String)>,
+  visited: &mut HashSet<String>,
 ) {
   if let Some(m) = module {
+    // `export *` may be circular
+    if !visited.insert(m.id()) {
+      return;
+    }
     for (name, path, orig) in &m.export_map {
       export_map.insert(name.clone(), (path.clone(), orig.clone()));
     }
-    // println!("export_wildcard {:?}", m.export_wildcard);
-    if !m.export_wildcard.is_empty() {
-      for src in &m.export_wildcard {
-        let module = mg.get_module_by_src(src);
-        // `if let some(...)` can prevent segmentation fault panic
-        if let Some(module) = module {
-          get_matches(Some(module), mg, export_map);
-        }
-      }
+    // export_wildcard are resolved abs_path of `export * from` sources
+    for abs_path in &m.export_wildcard {
+      let module = mg.modules.get(abs_path);
+      get_matches(module, mg, export_map, visited);
     }
   }
 }
@@ -68,10 +78,74 @@ pub struct ResolveModuleOptions {
   pub format: Option<Format>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleKind {
+  Script,
+  Asset,
+  Builtin,
+  NodeModules,
+  NotFound,
+  External,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+  /// import a from 'b'
+  Static,
+  /// import('b')
+  Dynamic,
+  /// import source a from 'b'
+  StaticSourcePhase,
+  /// import.source('b')
+  DynamicSourcePhase,
+  /// export { a } from 'b'
+  ReExport,
+}
+
+impl EdgeKind {
+  /// `import.meta` has no importee, it will not be an edge
+  pub fn from_import_type(t: ImportType) -> Option<EdgeKind> {
+    match t {
+      ImportType::Static => Some(EdgeKind::Static),
+      ImportType::Dynamic => Some(EdgeKind::Dynamic),
+      ImportType::StaticSourcePhase => Some(EdgeKind::StaticSourcePhase),
+      ImportType::DynamicSourcePhase => Some(EdgeKind::DynamicSourcePhase),
+      ImportType::ImportMeta => None,
+    }
+  }
+}
+
+/// 1-based position in source file
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Location {
+  pub line: usize,
+  pub column: usize,
+}
+
+/// Directed edge importer -> importee, both are keys of ModuleGraph.modules
+#[derive(Clone, Debug)]
+pub struct Edge {
+  pub from: String,
+  pub to: String,
+  pub kind: EdgeKind,
+  /// Original specifier in source code
+  pub specifier: String,
+  /// Imported names, `default` for default import and `*` for namespace or star re-export
+  pub names: Vec<String>,
+  /// Specifier in output, none if not rewritten
+  pub rewritten: Option<String>,
+  /// Position of the import statement or expression in importer
+  pub loc: Option<Location>,
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct Module {
-  /// The imported named of the module
+  /// The first specifier resolved to the module
   pub src: String,
+  /// All specifiers resolved to the module
+  pub specifiers: Vec<String>,
   /// is current module is compiled
   pub used: bool,
   /// Builtin node native modules
@@ -83,7 +157,6 @@ pub struct Module {
   /// Resolve failed
   pub not_found: bool,
   /// input files from options.include
-  #[allow(dead_code)]
   pub is_entry: bool,
   /// Resolved absolute filepath of src
   pub abs_path: String,
@@ -97,13 +170,59 @@ pub struct Module {
   pub export_wildcard: Vec<String>,
   /// has export star
   pub is_wildcard: bool,
+  /// Only contains imports with bindings and re-exports, see barrel_visitor
+  pub is_barrel: bool,
+  /// Keep src as bare specifier in output, e.g. workspace package with external strategy
+  pub external: bool,
+  /// is current module is written into output dir
+  pub emitted: bool,
+  /// Imports collected while compiling, type only imports are excluded
+  pub imports: Vec<ImportSpecifier>,
+  /// Exports collected while compiling, `ln` of exports with `src` is the name exported by `src`
+  pub exports: Vec<ExportSpecifier>,
 }
 
 impl Module {
+  /// Key of module in ModuleGraph.modules, specifier if it is not on file system
+  pub fn id(&self) -> String {
+    if self.abs_path.is_empty() {
+      self.src.clone()
+    } else {
+      self.abs_path.clone()
+    }
+  }
+  pub fn kind(&self) -> ModuleKind {
+    if self.built_in {
+      ModuleKind::Builtin
+    } else if self.external {
+      ModuleKind::External
+    } else if self.is_node_modules {
+      ModuleKind::NodeModules
+    } else if self.not_found {
+      ModuleKind::NotFound
+    } else if self.is_script {
+      ModuleKind::Script
+    } else {
+      ModuleKind::Asset
+    }
+  }
+  /// Real output filepath, same as Assets::output
+  pub fn output_path(&self) -> String {
+    if !self.is_script {
+      return self.v_abs_path.clone();
+    }
+    let path = self.v_abs_path.as_path();
+    path
+      .with_extension(output_ext(&self.v_abs_path))
+      .to_str()
+      .unwrap_or_default()
+      .to_string()
+  }
   // TODO: support custom ext
-  pub fn with_ext(&self, context: &str) -> String {
-    if self.built_in || self.is_node_modules || self.not_found {
-      return self.src.clone();
+  /// Specifier in output, `src` is the specifier in source code
+  pub fn with_ext(&self, src: &str, context: &str) -> String {
+    if self.built_in || self.is_node_modules || self.not_found || self.external {
+      return src.to_string();
     }
     let v_relative_path = self.relative(context);
     if !self.is_script {
@@ -112,7 +231,7 @@ impl Module {
     let path = v_relative_path.map(|f| {
       let path_str = f
         .as_path()
-        .with_extension("js")
+        .with_extension(output_ext(&f))
         .to_str()
         .unwrap_or_default()
         .to_string();
@@ -125,7 +244,7 @@ impl Module {
       let relative_path = self.v_abs_path.as_path().relative(context.as_path());
       let relative_path = relative_path.to_str();
       relative_path.map(|f| {
-        if f.starts_with(".") {
+        if f.starts_with('.') {
           f.to_string()
         } else {
           format!("./{}", f)
@@ -137,21 +256,148 @@ impl Module {
   }
 }
 
+/// `paths` mapped package configured in workspace_packages
+#[derive(Default, Clone, Debug)]
+pub struct WorkspacePackage {
+  pub name: String,
+  /// Directory contains package.json of the package
+  pub root: PathBuf,
+  pub strategy: WorkspaceStrategy,
+}
+
+impl WorkspacePackage {
+  /// Subpath of src in package exports e.g. `.` or `./utils`
+  pub fn subpath(&self, src: &str) -> Option<String> {
+    if src == self.name {
+      Some(".".into())
+    } else {
+      src
+        .strip_prefix(&format!("{}/", self.name))
+        .map(|f| format!("./{}", f))
+    }
+  }
+}
+
+/// (specifier, importer directory, format)
+type ResolveKey = (String, String, Format);
+
 #[derive(Default, Debug)]
 pub struct ModuleGraph {
   pub modules: HashMap<String, Module>,
+  /// Resolved module ids
+  resolved: HashMap<ResolveKey, String>,
   pub resolver: Resolver,
   pub config: Config,
   pub export_map: HashMap<String, HashMap<String, (String, String)>>,
+  pub workspace_packages: Vec<WorkspacePackage>,
+  pub edges: Vec<Edge>,
+  /// Indexes of edges keyed by importer
+  outgoing: HashMap<String, Vec<usize>>,
+  /// Indexes of edges keyed by importee
+  incoming: HashMap<String, Vec<usize>>,
+  /// Diagnostics reported while compiling, e.g. module boundary violations
+  pub diagnostics: Vec<Diagnostic>,
 }
 
 impl ModuleGraph {
   pub fn new(resolver: Resolver, config: Config) -> ModuleGraph {
+    let mut workspace_packages = vec![];
+    let mut diagnostics = vec![];
+    let context = config.options.root.to_str().unwrap_or_default().to_string();
+    for (name, strategy) in &config.resolved_options.workspace_packages {
+      let root = resolver
+        .resolve(name, &context, None)
+        .and_then(|f| f.abs_path)
+        .and_then(|f| find_up_package_root(f.as_path()));
+      match root {
+        Some(root) => workspace_packages.push(WorkspacePackage {
+          name: name.clone(),
+          root,
+          strategy: strategy.clone(),
+        }),
+        None => diagnostics.push(Diagnostic {
+          code: "workspace-package-not-found",
+          severity: Severity::Error,
+          message: format!(
+            "Workspace package `{}` can not be resolved, map it in tsconfig `paths`",
+            name
+          ),
+          file: None,
+          loc: None,
+        }),
+      }
+    }
     Self {
       modules: Default::default(),
+      resolved: Default::default(),
       resolver,
       config,
       export_map: Default::default(),
+      workspace_packages,
+      edges: Default::default(),
+      outgoing: Default::default(),
+      incoming: Default::default(),
+      diagnostics,
+    }
+  }
+  /// Path relative to project root for display, unchanged if outside of root
+  pub fn relative_to_root(&self, path: &str) -> String {
+    let root = &self.config.options.root;
+    if path.as_path().starts_with(root) {
+      path
+        .as_path()
+        .relative(root)
+        .to_str()
+        .unwrap_or_default()
+        .to_string()
+    } else {
+      path.to_string()
+    }
+  }
+  /// Module format `file` is emitted as, `.mts` and `.cts` override tsconfig `module`
+  fn output_format(&self, file: &str) -> Format {
+    match output_ext(file) {
+      "mjs" => Format::ESM,
+      "cjs" => Format::CJS,
+      _ => {
+        let module = self
+          .config
+          .tsconfig
+          .clone()
+          .and_then(|f| f.into_options().config.module);
+        match module {
+          Some(ModuleConfig::CommonJs(_)) | None => Format::CJS,
+          _ => Format::ESM,
+        }
+      }
+    }
+  }
+  fn get_workspace_package(&self, src: &str) -> Option<&WorkspacePackage> {
+    self
+      .workspace_packages
+      .iter()
+      .find(|f| f.subpath(src).is_some())
+  }
+  /// Rewrite abs_path based on output.dir
+  fn virtual_path(&self, abs_path: &str) -> String {
+    let inlined = self
+      .workspace_packages
+      .iter()
+      .find_map(|f| match &f.strategy {
+        WorkspaceStrategy::Inline { dest: Some(dest) } => abs_path
+          .as_path()
+          .strip_prefix(&f.root)
+          .ok()
+          .map(|suffix| self.config.resolved_options.output.join(dest).join(suffix)),
+        _ => None,
+      });
+    match inlined {
+      Some(path) => path.to_str().unwrap_or_default().to_string(),
+      None => replace_common_prefix(
+        abs_path.as_path(),
+        self.config.resolved_options.input.as_path(),
+        self.config.resolved_options.output.as_path(),
+      ),
     }
   }
   pub fn add_module(&mut self, abs_path: &str, module: Module) -> Option<&mut Module> {
@@ -174,6 +420,11 @@ impl ModuleGraph {
     // set_exports_info used for optimize packages, use mjs resolver by default(Format::ESM)
     let mut resolved_export_map = vec![];
     for (name, src, orig) in export_map {
+      // Declared by the module itself in wildcard mode
+      if src.is_empty() {
+        resolved_export_map.push((name, key.to_string(), orig));
+        continue;
+      }
       let module = self.resolve_module(ResolveModuleOptions {
         src: Some(src),
         context: key.to_string(),
@@ -211,31 +462,39 @@ impl ModuleGraph {
       m.export_wildcard = resolved_export_wildcards;
     }
   }
-  /// Mappings: { [src]: { [specifier]: [abs_path, orig] } }
-  pub fn get_mappings(&mut self, src: &str) -> Option<&HashMap<String, (String, String)>> {
-    if !self
-      .config
-      .resolved_options
-      .barrel_packages
-      .contains(&src.to_string())
-    {
-      return None;
+  /// Persist imports and exports collected from compiled module
+  pub fn set_module_specifiers(
+    &mut self,
+    id: &str,
+    imports: Vec<ImportSpecifier>,
+    exports: Vec<ExportSpecifier>,
+  ) {
+    if let Some(m) = self.modules.get_mut(id) {
+      m.imports = imports;
+      m.exports = exports;
     }
-    let size = self
-      .export_map
-      .get(src)
-      .and_then(|f| Some(f.len()))
-      .unwrap_or(0);
+  }
+  /// Mappings: { [id]: { [specifier]: [abs_path, orig] } }, id is the resolved barrel module
+  pub fn get_mappings(&mut self, id: &str) -> Option<&HashMap<String, (String, String)>> {
+    let size = self.export_map.get(id).map(|f| f.len()).unwrap_or(0);
     if size > 0 {
-      return self.export_map.get(src);
+      return self.export_map.get(id);
     };
     let mut export_map = HashMap::new();
-    let module = self.get_module_by_src(src);
-    get_matches(module, self, &mut export_map);
-    if size == 0 {
-      self.export_map.insert(src.to_string(), export_map);
+    let module = self.modules.get(id);
+    get_matches(module, self, &mut export_map, &mut HashSet::new());
+    self.export_map.insert(id.to_string(), export_map);
+    self.export_map.get(id)
+  }
+  /// Public specifier of file inside node_modules e.g. `@mui/material/Button`, validated against
+  /// `exports` of its package
+  pub fn public_specifier(&self, abs_path: &str) -> Option<String> {
+    let name = package_name_of_path(abs_path)?;
+    let subpath = PackageJson::read_installed(abs_path)?.subpath_of(abs_path.as_path())?;
+    match subpath.strip_prefix("./") {
+      Some(rest) => Some(format!("{}/{}", name, rest)),
+      None => Some(name),
     }
-    return self.export_map.get(src);
   }
   pub fn resolve_entry_module(
     &mut self,
@@ -248,12 +507,13 @@ impl ModuleGraph {
         path.to_str().unwrap_or_default().to_string()
       };
       let v_abs_path = abs_path.replace(
-        &self.config.resolved_options.input.to_str().unwrap(),
-        &self.config.resolved_options.output.to_str().unwrap(),
+        self.config.resolved_options.input.to_str().unwrap(),
+        self.config.resolved_options.output.to_str().unwrap(),
       );
       let m = Module {
-        src: sp,
-        v_abs_path: String::from(v_abs_path),
+        src: sp.clone(),
+        specifiers: vec![sp],
+        v_abs_path,
         abs_path: String::from(&abs_path),
         is_entry: true,
         is_script: SCRIPT_RE.is_match(&abs_path),
@@ -265,34 +525,116 @@ impl ModuleGraph {
       None
     }
   }
-  pub fn get_module(&mut self, options: ResolveModuleOptions) -> Option<&Module> {
-    let ResolveModuleOptions { src, specifier, .. } = options;
-    if let Some(src) = src.clone() {
-      // is barrel optimize
-      if self.config.resolved_options.barrel_packages.contains(&src) {
-        if let Some(specifier) = specifier {
-          if let Some(mappings) = self.get_mappings(&src) {
-            if let Some((abs_path, _)) = mappings.get(&specifier).cloned() {
-              if let Some(m) = self.modules.get(&abs_path) {
-                return Some(m);
-              }
-            }
-          }
+  /// Module declares `specifier` exported by barrel package `src`, along with the name exported
+  /// by that module, e.g. `default` for `export { default as Button } from './Button'`
+  pub fn get_module(&mut self, options: ResolveModuleOptions) -> Option<(&Module, String)> {
+    let ResolveModuleOptions {
+      src,
+      specifier,
+      context,
+      ..
+    } = options;
+    let (src, specifier) = (src?, specifier?);
+    // is barrel optimize
+    let id = if self.config.resolved_options.barrel_packages.contains(&src) {
+      // Barrel packages are optimized with mjs resolver
+      self
+        .resolve_module(ResolveModuleOptions {
+          src: Some(src),
+          context: context.clone(),
+          format: Some(Format::ESM),
+          ..Default::default()
+        })
+        .map(|m| m.id())?
+    } else {
+      self.resolve_local_barrel(&src, &context)?
+    };
+    let (abs_path, orig) = self.get_mappings(&id)?.get(&specifier).cloned()?;
+    // Import of barrel from the module it re-exports
+    if abs_path == context {
+      return None;
+    }
+    // Declared by the module itself in wildcard mode
+    let orig = if orig.is_empty() { specifier } else { orig };
+    self.modules.get(&abs_path).map(|m| (m, orig))
+  }
+  /// Resolved id of `src` if it is a local barrel, see `local_barrels` option
+  pub fn resolve_local_barrel(&mut self, src: &str, context: &str) -> Option<String> {
+    if !self.config.resolved_options.local_barrels {
+      return None;
+    }
+    let id = self
+      .resolve_module(ResolveModuleOptions {
+        src: Some(src.to_string()),
+        context: context.to_string(),
+        ..Default::default()
+      })
+      .map(|m| m.id())?;
+    self.is_local_barrel(&id).then_some(id)
+  }
+  /// Project module only contains re-exports, it is optimized on first check
+  fn is_local_barrel(&mut self, id: &str) -> bool {
+    if !self
+      .modules
+      .get(id)
+      .is_some_and(|m| m.kind() == ModuleKind::Script)
+    {
+      return false;
+    }
+    let path = self.relative_to_root(id);
+    let is_excluded = self
+      .config
+      .resolved_options
+      .exclude_barrels
+      .iter()
+      .filter_map(|f| Pattern::new(f).ok())
+      .any(|p| p.matches(&path));
+    if is_excluded {
+      return false;
+    }
+    let m = self.modules.get_mut(id).expect("module should exist");
+    if !m.optimized {
+      m.optimized = true;
+      debug!(target: "tswc", "detect local barrel {:?}", id);
+      optimize(id, self, Some(false));
+      self.optimize_wildcard_modules();
+    }
+    self.modules[id].is_barrel
+  }
+  /// Collect exports info of `export *` targets, they are marked as wildcard by set_exports_info
+  pub fn optimize_wildcard_modules(&mut self) {
+    while self.get_wildcard_modules_size() != 0 {
+      let paths_to_compile: Vec<_> = {
+        let unused_modules = self.get_wildcard_modules();
+        unused_modules
+          .map(|decl| {
+            decl.optimized = true;
+            debug!(
+                target: "tswc",
+                "optimize! {:?}", &decl.abs_path
+            );
+            (decl.abs_path.clone(), decl.is_script, decl.is_wildcard)
+          })
+          .collect()
+      };
+      for (resolved_path, is_script, is_wildcard) in paths_to_compile {
+        if is_script {
+          optimize(&resolved_path, self, Some(is_wildcard));
         }
       }
     }
-    return None;
   }
   pub fn resolve_context(&self, context: &str) -> String {
     let dir = self.resolver.resolve_context(context).unwrap_or_default();
     let v_context = replace_common_prefix(
-      &dir.as_path(),
-      &self.config.resolved_options.input.as_path(),
-      &self.config.resolved_options.output.as_path(),
+      dir.as_path(),
+      self.config.resolved_options.input.as_path(),
+      self.config.resolved_options.output.as_path(),
     );
     v_context
   }
   /// Resolved module added into self.modules
+  /// the same specifier from the same directory with the same format is resolved only once
   pub fn resolve_module(&mut self, options: ResolveModuleOptions) -> Option<&mut Module> {
     let ResolveModuleOptions {
       src,
@@ -303,75 +645,159 @@ impl ModuleGraph {
     } = options;
     // TODO: currently we resolve and add every module during compile
     // should we only resolve and add every module config in paths
-    // TODO: should skip resolve if src and context found in module graph
-    if let Some(src) = src {
-      let module = match self.resolver.resolve(&src, &context, format) {
-        Some(resolved) => {
-          let abs_path: String = resolved
-            .abs_path
-            .and_then(|f| {
-              // Webpack support add query on file suffix e.g. import svg from "path/icon.svg?url"
-              // should clean path prevent unable to find real path on file system
-              let p = clean_path(&f);
-              return Some(p);
-            })
-            .unwrap_or("".into());
-          let v_abs_path = replace_common_prefix(
-            abs_path.as_path(),
-            &self.config.resolved_options.input.as_path(),
-            &self.config.resolved_options.output.as_path(),
-          );
-          let is_script = SCRIPT_RE.is_match(&abs_path);
-          debug!(
-            target: "tswc",
-            "abs_path {:?} v_abs_path {:?} is_script {:?}",
-            abs_path, v_abs_path, is_script
-          );
-          let m = Module {
-            src,
-            is_script,
-            abs_path: abs_path.clone(),
-            v_abs_path: v_abs_path.into(),
-            // TODO: maybe renamed to skip compile
-            used: resolved.built_in || resolved.is_node_modules || resolved.not_found,
-            is_node_modules: resolved.is_node_modules,
-            not_found: resolved.not_found,
-            built_in: resolved.built_in,
-            is_wildcard: is_wildcard.unwrap_or(false),
-            ..Default::default()
-          };
-          // FIXME: if abs_path releated is already inserted; self.add_module take no effect
-          // modify m after resolve_module will not working on self.modules[abs_path]
-          // and cloned module here, it mean m !== self.modules[abs_path]
-          self.add_module(&abs_path, m)
+    let src = src?;
+    // Defaults to how importer is emitted, e.g. built output is picked by `import` condition for ES module
+    let output_format = format
+      .clone()
+      .unwrap_or_else(|| self.output_format(&context));
+    let dir = self.resolver.resolve_context(&context).unwrap_or_default();
+    let key: ResolveKey = (src.clone(), dir, output_format.clone());
+    if let Some(id) = self.resolved.get(&key) {
+      return self.modules.get_mut(id);
+    }
+    let workspace_package = self.get_workspace_package(&src).cloned();
+    let m = match &workspace_package {
+      Some(pkg) if pkg.strategy == WorkspaceStrategy::External => Module {
+        src: src.clone(),
+        used: true,
+        external: true,
+        ..Default::default()
+      },
+      _ => {
+        let resolved = self.resolver.resolve(&src, &context, format)?;
+        let abs_path: String = resolved
+          .abs_path
+          .map(|f| {
+            // Webpack support add query on file suffix e.g. import svg from "path/icon.svg?url"
+            // should clean path prevent unable to find real path on file system
+            clean_path(&f)
+          })
+          .unwrap_or("".into());
+        // Point to built output of workspace package instead of compile it
+        let built_output = workspace_package
+          .filter(|f| f.strategy == WorkspaceStrategy::Output)
+          .and_then(|f| {
+            let subpath = f.subpath(&src)?;
+            PackageJson::read(&f.root)?.entry(&subpath, &output_format)
+          })
+          .map(|f| f.to_str().unwrap_or_default().to_string());
+        let v_abs_path = built_output
+          .clone()
+          .unwrap_or_else(|| self.virtual_path(&abs_path));
+        let is_script = SCRIPT_RE.is_match(&abs_path);
+        debug!(
+          target: "tswc",
+          "abs_path {:?} v_abs_path {:?} is_script {:?}",
+          abs_path, v_abs_path, is_script
+        );
+        Module {
+          src: src.clone(),
+          is_script,
+          abs_path,
+          v_abs_path,
+          // TODO: maybe renamed to skip compile
+          used: resolved.built_in
+            || resolved.is_node_modules
+            || resolved.not_found
+            || built_output.is_some(),
+          is_node_modules: resolved.is_node_modules,
+          not_found: resolved.not_found,
+          built_in: resolved.built_in,
+          is_wildcard: is_wildcard.unwrap_or(false),
+          ..Default::default()
         }
-        None => None,
-      };
-      module
-    } else {
-      None
+      }
+    };
+    let id = m.id();
+    self.resolved.insert(key, id.clone());
+    // FIXME: if abs_path releated is already inserted; self.add_module take no effect
+    // modify m after resolve_module will not working on self.modules[abs_path]
+    // and cloned module here, it mean m !== self.modules[abs_path]
+    let module = self.add_module(&id, m)?;
+    if !module.specifiers.contains(&src) {
+      module.specifiers.push(src);
     }
+    Some(module)
   }
-  pub fn get_module_by_src(&self, src: &str) -> Option<&Module> {
-    self.modules.values().find(|m| m.src == src)
+  /// Add edge importer -> importee, names are merged into existed edge with same specifier and kind.
+  /// Returns whether a new edge is added
+  pub fn add_edge(&mut self, edge: Edge) -> bool {
+    let existed = self.outgoing.get(&edge.from).and_then(|indexes| {
+      indexes.iter().copied().find(|i| {
+        let e = &self.edges[*i];
+        e.to == edge.to && e.kind == edge.kind && e.specifier == edge.specifier
+      })
+    });
+    if let Some(i) = existed {
+      let e = &mut self.edges[i];
+      for name in edge.names {
+        if !e.names.contains(&name) {
+          e.names.push(name);
+        }
+      }
+      if e.rewritten.is_none() {
+        e.rewritten = edge.rewritten;
+      }
+      return false;
+    }
+    let i = self.edges.len();
+    self.outgoing.entry(edge.from.clone()).or_default().push(i);
+    self.incoming.entry(edge.to.clone()).or_default().push(i);
+    self.edges.push(edge);
+    true
+  }
+  /// Edges from module
+  pub fn dependencies(&self, id: &str) -> Vec<&Edge> {
+    self
+      .outgoing
+      .get(id)
+      .map(|indexes| indexes.iter().map(|i| &self.edges[*i]).collect())
+      .unwrap_or_default()
+  }
+  /// Edges to module
+  pub fn dependents(&self, id: &str) -> Vec<&Edge> {
+    self
+      .incoming
+      .get(id)
+      .map(|indexes| indexes.iter().map(|i| &self.edges[*i]).collect())
+      .unwrap_or_default()
+  }
+  fn closure(&self, id: &str, index: &HashMap<String, Vec<usize>>, forward: bool) -> Vec<String> {
+    let mut visited: HashSet<String> = HashSet::from([id.to_string()]);
+    let mut queue = VecDeque::from([id.to_string()]);
+    let mut result = vec![];
+    while let Some(current) = queue.pop_front() {
+      for i in index.get(&current).into_iter().flatten() {
+        let edge = &self.edges[*i];
+        let next = if forward { &edge.to } else { &edge.from };
+        if visited.insert(next.clone()) {
+          result.push(next.clone());
+          queue.push_back(next.clone());
+        }
+      }
+    }
+    result
+  }
+  /// Modules reachable from module, in breadth first order
+  #[allow(dead_code)]
+  pub fn transitive_dependencies(&self, id: &str) -> Vec<String> {
+    self.closure(id, &self.outgoing, true)
+  }
+  /// Modules depend on module directly or indirectly, in breadth first order
+  pub fn transitive_dependents(&self, id: &str) -> Vec<String> {
+    self.closure(id, &self.incoming, false)
   }
   pub fn get_unused_modules(&mut self) -> impl Iterator<Item = &mut Module> {
     self.modules.values_mut().filter(|module| !module.used)
   }
   pub fn get_unused_modules_size(&self) -> usize {
-    let modules: Vec<&Module> = self
-      .modules
-      .values()
-      .into_iter()
-      .filter(|f| !f.used)
-      .collect();
+    let modules: Vec<&Module> = self.modules.values().filter(|f| !f.used).collect();
     modules.len()
   }
   pub fn get_wildcard_modules_size(&self) -> usize {
     let modules: Vec<&Module> = self
       .modules
       .values()
-      .into_iter()
       .filter(|f| f.is_wildcard && !f.optimized)
       .collect();
     modules.len()
@@ -383,3 +809,47 @@ impl ModuleGraph {
       .filter(|module| module.is_wildcard && !module.optimized)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// index -> a -> b -> c -> a, index -> d
+  fn graph() -> ModuleGraph {
+    let mut mg = ModuleGraph::default();
+    for (from, to) in [
+      ("index", "a"),
+      ("a", "b"),
+      ("b", "c"),
+      ("c", "a"),
+      ("index", "d"),
+    ] {
+      mg.add_edge(Edge {
+        from: from.into(),
+        to: to.into(),
+        kind: EdgeKind::Static,
+        specifier: format!("./{}", to),
+        names: vec![],
+        rewritten: None,
+        loc: None,
+      });
+    }
+    mg
+  }
+
+  #[test]
+  fn transitive_dependencies() {
+    let mg = graph();
+    assert_eq!(mg.transitive_dependencies("index"), ["a", "d", "b", "c"]);
+    assert_eq!(mg.transitive_dependencies("b"), ["c", "a"]);
+    assert!(mg.transitive_dependencies("d").is_empty());
+  }
+
+  #[test]
+  fn transitive_dependents() {
+    let mg = graph();
+    assert_eq!(mg.transitive_dependents("b"), ["a", "index", "c"]);
+    assert_eq!(mg.transitive_dependents("d"), ["index"]);
+    assert!(mg.transitive_dependents("index").is_empty());
+  }
+}
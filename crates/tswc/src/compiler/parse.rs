@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use swc_core::common::{FileName, FilePathMapping, SourceFile, SourceMap};
+use swc_core::ecma::ast::EsVersion;
+use swc_core::ecma::parser::{parse_file_as_module, EsSyntax, Syntax, TsSyntax};
+use swc_core::ecma::visit::VisitMutWith;
+
+use crate::plugins::ImportExportVisitor;
+use crate::utils::{ExportSpecifier, ImportSpecifier};
+
+/// Imports and exports of a single module, spans are relative to `fm`
+pub struct ParsedModule {
+  pub fm: Arc<SourceFile>,
+  pub imports: Vec<ImportSpecifier>,
+  pub exports: Vec<ExportSpecifier>,
+  pub facade: bool,
+  pub has_module_syntax: bool,
+}
+
+fn syntax_of(filename: &str) -> Syntax {
+  let ext = filename
+    .rsplit_once('.')
+    .map(|(_, ext)| ext)
+    .unwrap_or_default();
+  match ext {
+    "ts" | "mts" | "cts" => Syntax::Typescript(TsSyntax {
+      decorators: true,
+      ..Default::default()
+    }),
+    "js" | "jsx" | "mjs" | "cjs" => Syntax::Es(EsSyntax {
+      jsx: true,
+      decorators: true,
+      import_attributes: true,
+      ..Default::default()
+    }),
+    _ => Syntax::Typescript(TsSyntax {
+      tsx: true,
+      decorators: true,
+      ..Default::default()
+    }),
+  }
+}
+
+/// Collect imports and exports without resolving, syntax is detected from extension of `filename`
+pub fn parse(code: String, filename: &str) -> Result<ParsedModule, String> {
+  let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+  let fm = cm.new_source_file(Arc::new(FileName::Real(PathBuf::from(filename))), code);
+  let mut module = parse_file_as_module(
+    &fm,
+    syntax_of(filename),
+    EsVersion::EsNext,
+    None,
+    &mut vec![],
+  )
+  .map_err(|err| format!("Failed to parse {}: {:?}", filename, err.kind().msg()))?;
+  let mut visitor = ImportExportVisitor::new(None, filename.to_string(), cm);
+  module.visit_mut_with(&mut visitor);
+  Ok(ParsedModule {
+    fm,
+    imports: visitor.imports,
+    exports: visitor.exports,
+    facade: visitor.facade,
+    has_module_syntax: visitor.has_module_syntax,
+  })
+}
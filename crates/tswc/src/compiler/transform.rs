@@ -2,15 +2,18 @@ use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 
-use super::{ModuleGraph, SwcCompiler};
+use super::{EdgeKind, ModuleGraph, SwcCompiler};
+use crate::analysis::{Diagnostic, Severity};
 use crate::plugins::{
-  Barrel, BarrelConfig, ImportExportVisitor, NamedImportTransform, NamedImportTransformConfig,
+  Barrel, BarrelConfig, ImportExportVisitor, ModularizeImports, ModularizeImportsConfig,
+  NamedImportTransform, NamedImportTransformConfig,
 };
+use crate::utils::output_ext;
 use swc_core::base::config::{Config, JsMinifyFormatOptions, JscConfig, ModuleConfig, Options};
 use swc_core::base::TransformOutput;
 use swc_core::common::{chain, comments::Comments, Mark, SourceMap};
 use swc_core::ecma::{
-  ast::EsVersion,
+  ast::{EsVersion, Program},
   transforms::base::pass::noop,
   visit::{as_folder, Fold},
 };
@@ -18,24 +21,36 @@ use tsconfig::TsConfig;
 
 #[allow(clippy::too_many_arguments)]
 pub fn transform<'a>(
+  program: &Program,
   _resource_path: &'a Path,
   _comments: Option<&'a dyn Comments>,
   _top_level_mark: Mark,
   _unresolved_mark: Mark,
-  _cm: Arc<SourceMap>,
+  cm: Arc<SourceMap>,
   module_graph: &'a mut ModuleGraph,
   context: String,
 ) -> impl Fold + 'a {
+  let modularize_imports_visitor = ModularizeImports::new(ModularizeImportsConfig {
+    packages: module_graph
+      .config
+      .resolved_options
+      .modularize_imports
+      .clone(),
+  });
   let packages = module_graph.config.resolved_options.barrel_packages.clone();
-  let export_import_visitor = ImportExportVisitor::new(module_graph, context);
+  let mut export_import_visitor = ImportExportVisitor::new(Some(module_graph), context, cm);
+  // Kinds of enums and type only exports are lost after typescript is stripped
+  if let Program::Module(module) = program {
+    export_import_visitor.detect_declarations(module);
+  }
   let named_import_transform_visitor =
     NamedImportTransform::new(NamedImportTransformConfig { packages });
-  let ch = chain!(
+  chain!(
+    modularize_imports_visitor,
     named_import_transform_visitor,
     as_folder(export_import_visitor),
     noop()
-  );
-  ch
+  )
 }
 
 pub trait IntoOptions {
@@ -47,70 +62,57 @@ impl IntoOptions for TsConfig {
     let target = self
       .compiler_options
       .as_ref()
-      .and_then(|f| match &f.target {
-        Some(target) => {
-          let target = match target {
-            tsconfig::Target::Es3 => EsVersion::Es3,
-            tsconfig::Target::Es5 => EsVersion::Es5,
-            tsconfig::Target::Es6 => EsVersion::Es2015,
-            tsconfig::Target::Es7 => EsVersion::Es2016,
-            tsconfig::Target::Es2015 => EsVersion::Es2015,
-            tsconfig::Target::Es2016 => EsVersion::Es2016,
-            tsconfig::Target::Es2017 => EsVersion::Es2017,
-            tsconfig::Target::Es2018 => EsVersion::Es2018,
-            tsconfig::Target::Es2019 => EsVersion::Es2019,
-            tsconfig::Target::Es2020 => EsVersion::Es2020,
-            tsconfig::Target::EsNext => EsVersion::EsNext,
-            tsconfig::Target::Other(target) => match target.as_str() {
-              "ES2021" => EsVersion::Es2021,
-              "ES2022" => EsVersion::Es2022,
-              _ => EsVersion::Es3,
-            },
-          };
-          Some(target)
-        }
-        None => Some(EsVersion::Es3),
+      .map(|f| match &f.target {
+        Some(target) => match target {
+          tsconfig::Target::Es3 => EsVersion::Es3,
+          tsconfig::Target::Es5 => EsVersion::Es5,
+          tsconfig::Target::Es6 => EsVersion::Es2015,
+          tsconfig::Target::Es7 => EsVersion::Es2016,
+          tsconfig::Target::Es2015 => EsVersion::Es2015,
+          tsconfig::Target::Es2016 => EsVersion::Es2016,
+          tsconfig::Target::Es2017 => EsVersion::Es2017,
+          tsconfig::Target::Es2018 => EsVersion::Es2018,
+          tsconfig::Target::Es2019 => EsVersion::Es2019,
+          tsconfig::Target::Es2020 => EsVersion::Es2020,
+          tsconfig::Target::EsNext => EsVersion::EsNext,
+          tsconfig::Target::Other(target) => match target.as_str() {
+            "ES2021" => EsVersion::Es2021,
+            "ES2022" => EsVersion::Es2022,
+            _ => EsVersion::Es3,
+          },
+        },
+        None => EsVersion::Es3,
       })
       .unwrap_or(EsVersion::Es3);
-    let module = self
-      .compiler_options
-      .as_ref()
-      .and_then(|f| match &f.module {
-        Some(module) => {
-          let module = match module {
-            tsconfig::Module::CommonJs => ModuleConfig::CommonJs(Default::default()),
-            tsconfig::Module::Amd => ModuleConfig::Amd(Default::default()),
-            tsconfig::Module::Umd => ModuleConfig::Umd(Default::default()),
-            tsconfig::Module::System => ModuleConfig::SystemJs(Default::default()),
-            tsconfig::Module::Es6 => ModuleConfig::Es6(Default::default()),
-            tsconfig::Module::EsNext => ModuleConfig::Es6(Default::default()),
-            tsconfig::Module::Es2015 => ModuleConfig::Es6(Default::default()),
-            tsconfig::Module::Es2020 => ModuleConfig::Es6(Default::default()),
-            tsconfig::Module::Other(module) => {
-              if module == "Node16" || module == "NodeNext" {
-                ModuleConfig::NodeNext(Default::default())
-              } else {
-                let m = if target == EsVersion::Es3 || target == EsVersion::Es5 {
-                  ModuleConfig::CommonJs(Default::default())
-                } else {
-                  ModuleConfig::Es6(Default::default())
-                };
-                m
-              }
-            }
-            tsconfig::Module::None => {
-              let m = if target == EsVersion::Es3 || target == EsVersion::Es5 {
-                ModuleConfig::CommonJs(Default::default())
-              } else {
-                ModuleConfig::Es6(Default::default())
-              };
-              m
-            }
-          };
-          Some(module)
+    let module = self.compiler_options.as_ref().map(|f| match &f.module {
+      Some(module) => match module {
+        tsconfig::Module::CommonJs => ModuleConfig::CommonJs(Default::default()),
+        tsconfig::Module::Amd => ModuleConfig::Amd(Default::default()),
+        tsconfig::Module::Umd => ModuleConfig::Umd(Default::default()),
+        tsconfig::Module::System => ModuleConfig::SystemJs(Default::default()),
+        tsconfig::Module::Es6 => ModuleConfig::Es6(Default::default()),
+        tsconfig::Module::EsNext => ModuleConfig::Es6(Default::default()),
+        tsconfig::Module::Es2015 => ModuleConfig::Es6(Default::default()),
+        tsconfig::Module::Es2020 => ModuleConfig::Es6(Default::default()),
+        tsconfig::Module::Other(module) => {
+          if module == "Node16" || module == "NodeNext" {
+            ModuleConfig::NodeNext(Default::default())
+          } else if target == EsVersion::Es3 || target == EsVersion::Es5 {
+            ModuleConfig::CommonJs(Default::default())
+          } else {
+            ModuleConfig::Es6(Default::default())
+          }
+        }
+        tsconfig::Module::None => {
+          if target == EsVersion::Es3 || target == EsVersion::Es5 {
+            ModuleConfig::CommonJs(Default::default())
+          } else {
+            ModuleConfig::Es6(Default::default())
+          }
         }
-        None => Some(ModuleConfig::CommonJs(Default::default())),
-      });
+      },
+      None => ModuleConfig::CommonJs(Default::default()),
+    });
 
     Options {
       config: Config {
@@ -127,14 +129,19 @@ impl IntoOptions for TsConfig {
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn compile<'a>(resource_path: &str, mut module_graph: &'a mut ModuleGraph) -> TransformOutput {
-  let options = module_graph.config.tsconfig.clone().unwrap().into_options();
+pub fn compile(resource_path: &str, module_graph: &mut ModuleGraph) -> TransformOutput {
+  let mut options = module_graph.config.tsconfig.clone().unwrap().into_options();
+  // `.mts` and `.cts` are emitted as `.mjs` and `.cjs`, so their module kind can't follow tsconfig
+  match output_ext(resource_path) {
+    "mjs" => options.config.module = Some(ModuleConfig::Es6(Default::default())),
+    "cjs" => options.config.module = Some(ModuleConfig::CommonJs(Default::default())),
+    _ => {}
+  }
+  let is_commonjs = matches!(options.config.module, Some(ModuleConfig::CommonJs(_)));
   // to absolute path
   let resource_path = Path::new(resource_path).canonicalize().expect("TODO:");
   let source = fs::read_to_string(&resource_path).expect("failed to read file");
-  let c = SwcCompiler::new(resource_path.to_path_buf(), source.clone(), options)
-    .map_err(|err| anyhow::Error::from(err))
-    .expect("TODO:");
+  let c = SwcCompiler::new(resource_path.to_path_buf(), source.clone(), options).expect("TODO:");
   let options = c.options();
   let top_level_mark = options
     .top_level_mark
@@ -144,22 +151,23 @@ pub fn compile<'a>(resource_path: &str, mut module_graph: &'a mut ModuleGraph) -
     .expect("`unresolved_mark` should be initialized");
 
   let built = c
-    .parse(None, |_| {
+    .parse(None, |program| {
       transform(
+        program,
         &resource_path,
         Some(c.comments()),
         top_level_mark,
         unresolved_mark,
         c.cm().clone(),
-        &mut module_graph,
+        module_graph,
         resource_path.to_str().unwrap().to_string(),
       )
     })
     .expect("TODO:");
-  let program = c
-    .transform(built)
-    .map_err(|err| anyhow::Error::from(err))
-    .expect("TODO:");
+  let program = c.transform(built).expect("TODO:");
+  if is_commonjs {
+    check_require_esm(module_graph, resource_path.to_str().unwrap_or_default());
+  }
   let format_opt = JsMinifyFormatOptions {
     ..Default::default()
   };
@@ -176,6 +184,29 @@ pub fn compile<'a>(resource_path: &str, mut module_graph: &'a mut ModuleGraph) -
   output.unwrap()
 }
 
+/// Imports of CommonJS output, including `import()`, are `require` calls which can't load `.mjs`
+/// ES modules
+fn check_require_esm(module_graph: &mut ModuleGraph, id: &str) {
+  let diagnostics: Vec<Diagnostic> = module_graph
+    .dependencies(id)
+    .into_iter()
+    .filter(|e| matches!(e.kind, EdgeKind::Static | EdgeKind::ReExport | EdgeKind::Dynamic))
+    .filter(|e| output_ext(&e.to) == "mjs")
+    .map(|e| Diagnostic {
+      code: "require-esm",
+      severity: Severity::Error,
+      message: format!(
+        "ES module `{}` is required by CommonJS output of {}, compile the importer as ES module or set tsconfig `module` to `NodeNext`",
+        e.specifier,
+        module_graph.relative_to_root(id)
+      ),
+      file: Some(id.to_string()),
+      loc: e.loc,
+    })
+    .collect();
+  module_graph.diagnostics.extend(diagnostics);
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn transform_with_optimize<'a>(
   _resource_path: &'a Path,
@@ -188,24 +219,21 @@ pub fn transform_with_optimize<'a>(
   wildcard: bool,
 ) -> impl Fold + 'a {
   let barrel = Barrel::new(module_graph, context, BarrelConfig { wildcard });
-  let ch = chain!(barrel, noop());
-  ch
+  chain!(barrel, noop())
 }
 
 // Do some optimization.
 // Job: Barrel optimize
-pub fn optimize<'a>(
+pub fn optimize(
   resource_path: &str,
-  mut module_graph: &'a mut ModuleGraph,
+  module_graph: &mut ModuleGraph,
   wildcard: Option<bool>,
 ) -> TransformOutput {
   let options = module_graph.config.tsconfig.clone().unwrap().into_options();
   // to absolute path
   let resource_path = Path::new(resource_path).canonicalize().expect("TODO:");
   let source = fs::read_to_string(&resource_path).expect("failed to read file");
-  let c = SwcCompiler::new(resource_path.to_path_buf(), source.clone(), options)
-    .map_err(|err| anyhow::Error::from(err))
-    .expect("TODO:");
+  let c = SwcCompiler::new(resource_path.to_path_buf(), source.clone(), options).expect("TODO:");
   let options = c.options();
   let top_level_mark = options
     .top_level_mark
@@ -222,16 +250,13 @@ pub fn optimize<'a>(
         top_level_mark,
         unresolved_mark,
         c.cm().clone(),
-        &mut module_graph,
+        module_graph,
         resource_path.to_str().unwrap().to_string(),
         wildcard.unwrap_or(false),
       )
     })
     .expect("TODO:");
-  let program = c
-    .transform(built)
-    .map_err(|err| anyhow::Error::from(err))
-    .expect("TODO:");
+  let program = c.transform(built).expect("TODO:");
 
   let format_opt = JsMinifyFormatOptions {
     ..Default::default()
@@ -1,27 +1,89 @@
 use log::debug;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use sugar_path::SugarPath;
 
 use ignore::{overrides::OverrideBuilder, WalkBuilder};
 use tsconfig::TsConfig;
 
-#[derive(Debug)]
+use crate::analysis::Severity;
+use crate::resolver::PackageJson;
+use crate::utils::{DTS_RE, SCRIPT_RE};
+
+const SOURCE_EXTENSIONS: [&str; 8] = ["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+/// How a `paths` mapped workspace package outside of root is emitted
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkspaceStrategy {
+  /// Compile package sources into output dir, `dest` is relative to output dir
+  Inline { dest: Option<String> },
+  /// Keep the bare specifier, package will be installed
+  External,
+  /// Rewrite to package built output declared by package.json `main`/`exports`
+  Output,
+}
+
+impl Default for WorkspaceStrategy {
+  fn default() -> Self {
+    WorkspaceStrategy::Inline { dest: None }
+  }
+}
+
+/// How barrel packages are handled if modules skipped by optimization may have side effects
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BarrelSideEffects {
+  /// Optimize and report a warning
+  #[default]
+  Warn,
+  /// Do not optimize the package and report a warning
+  Skip,
+  /// Optimize silently
+  Ignore,
+}
+
+/// Imports of package rewritten to the module of each imported member, without resolving the package
+#[derive(Debug, Clone, Default)]
+pub struct ModularizeImport {
+  /// Template of module path e.g. `lodash/{{member}}` or `@icons/{{kebabCase member}}`
+  pub transform: String,
+  /// Keep named imports, otherwise they are converted to default imports
+  pub skip_default_conversion: bool,
+  /// Templates of specific members, keyed by imported name
+  pub members: HashMap<String, String>,
+}
+
+/// Imports from modules match `from` to targets match `to` are not allowed, unless importer matches `except`
+#[derive(Debug, Clone, Default)]
+pub struct BoundaryRule {
+  /// Globs of importers relative to root, every module if empty
+  pub from: Vec<String>,
+  /// Globs of targets relative to root, or package names e.g. `pg`
+  pub to: Vec<String>,
+  /// Globs of importers relative to root allowed to import targets
+  pub except: Vec<String>,
+  pub message: Option<String>,
+  pub severity: Severity,
+}
+
+#[derive(Debug, Default)]
 pub struct ConfigOptions {
   pub output: Option<String>,
   pub root: PathBuf,
   pub exclude: Option<Vec<String>>,
+  /// Entry files relative to root, only modules reachable from them are compiled
+  pub entries: Option<Vec<String>>,
+  /// Fallback entries to sources of package.json `main`/`exports` targets if `entries` is not set,
+  /// every searched file is an entry if neither is found
+  pub package_entries: bool,
   pub barrel_packages: Vec<String>,
-}
-
-impl Default for ConfigOptions {
-  fn default() -> Self {
-    Self {
-      output: None,
-      root: PathBuf::default(),
-      exclude: None,
-      barrel_packages: Default::default(),
-    }
-  }
+  /// Detect pure barrel files of project, imports through them are rewritten to underlying modules
+  pub local_barrels: bool,
+  /// Globs relative to root, matched files are never optimized as local barrels
+  pub exclude_barrels: Vec<String>,
+  pub modularize_imports: HashMap<String, ModularizeImport>,
+  pub workspace_packages: HashMap<String, WorkspaceStrategy>,
+  /// Module boundary rules checked against every resolved import
+  pub rules: Vec<BoundaryRule>,
 }
 
 #[derive(Default, Debug)]
@@ -29,7 +91,13 @@ pub struct ResolvedConfigOptions {
   pub input: PathBuf,
   pub output: PathBuf,
   pub exclude: Vec<String>,
+  /// Absolute entry files, `None` means every searched file is an entry
+  pub entries: Option<Vec<PathBuf>>,
   pub barrel_packages: Vec<String>,
+  pub local_barrels: bool,
+  pub exclude_barrels: Vec<String>,
+  pub modularize_imports: HashMap<String, ModularizeImport>,
+  pub workspace_packages: HashMap<String, WorkspaceStrategy>,
 }
 
 #[derive(Default, Debug)]
@@ -50,12 +118,12 @@ impl Config {
   pub fn resolve_options(&mut self, tsconfig_file_path: &Path) {
     self.parse_tsconfig(tsconfig_file_path);
     let mut input: PathBuf = self.options.root.clone();
-    let mut exclude = self.options.exclude.clone().unwrap_or(vec![]);
+    let mut exclude = self.options.exclude.clone().unwrap_or_default();
     let default_output: PathBuf = input.join("dist");
     let mut output = default_output.clone();
 
     if let Some(tsconfig) = &self.tsconfig {
-      let include = { tsconfig.include.as_ref().and_then(|f| f.get(0)) };
+      let include = { tsconfig.include.as_ref().and_then(|f| f.first()) };
       input = if let Some(include) = include {
         self.options.root.join(include)
       } else {
@@ -68,7 +136,7 @@ impl Config {
           .compiler_options
           .as_ref()
           .and_then(|f| f.out_dir.as_ref())
-          .and_then(|f| Some(self.options.root.join(f)))
+          .map(|f| self.options.root.join(f))
           .unwrap_or(default_output)
       };
 
@@ -82,14 +150,66 @@ impl Config {
           .to_vec()
       };
     }
+    let mut entries = self.options.entries.as_ref().map(|entries| {
+      entries
+        .iter()
+        .map(|f| self.options.root.join(f).normalize())
+        .collect()
+    });
+    if entries.is_none() && self.options.package_entries {
+      let package_entries = self.package_entries(&input, &output);
+      entries = (!package_entries.is_empty()).then_some(package_entries);
+    }
     let resolved_options = ResolvedConfigOptions {
       input,
       output,
       exclude,
+      entries,
       barrel_packages: self.options.barrel_packages.clone(),
+      local_barrels: self.options.local_barrels,
+      exclude_barrels: self.options.exclude_barrels.clone(),
+      modularize_imports: self.options.modularize_imports.clone(),
+      workspace_packages: self.options.workspace_packages.clone(),
     };
     self.resolved_options = resolved_options;
   }
+  /// Public entries of package, configured `entries` or sources of package.json targets
+  pub fn public_entries(&self) -> Vec<PathBuf> {
+    match &self.resolved_options.entries {
+      Some(entries) => entries.clone(),
+      None => self.package_entries(&self.resolved_options.input, &self.resolved_options.output),
+    }
+  }
+  /// Map package.json targets back to source files, e.g. `dist/index.js` to `src/index.ts`
+  fn package_entries(&self, input: &Path, output: &Path) -> Vec<PathBuf> {
+    let Some(pkg) = PackageJson::read(&self.options.root) else {
+      return vec![];
+    };
+    let mut entries = vec![];
+    for target in pkg.targets() {
+      let target_str = target.to_str().unwrap_or_default();
+      if DTS_RE.is_match(target_str) || !SCRIPT_RE.is_match(target_str) {
+        continue;
+      }
+      let source = match target.strip_prefix(output) {
+        Ok(relative) => input.join(relative),
+        Err(_) => target.clone(),
+      };
+      let found = std::iter::once(source.clone())
+        .chain(
+          SOURCE_EXTENSIONS
+            .iter()
+            .map(|ext| source.with_extension(ext)),
+        )
+        .find(|f| f.is_file());
+      if let Some(found) = found {
+        if !entries.contains(&found) {
+          entries.push(found);
+        }
+      }
+    }
+    entries
+  }
   pub fn search_files(&mut self) {
     let root = &self.resolved_options.input;
     let mut ignores = self
@@ -106,8 +226,14 @@ impl Config {
       "**/*.tsx".into(),
       "**/*.js".into(),
       "**/*.jsx".into(),
+      "**/*.mts".into(),
+      "**/*.cts".into(),
+      "**/*.mjs".into(),
+      "**/*.cjs".into(),
       "!node_modules".into(),
       "!**/*.d.ts".into(),
+      "!**/*.d.mts".into(),
+      "!**/*.d.cts".into(),
     ];
     globs.append(&mut ignores);
     debug!(target: "tswc", "globs {:?}", globs);
@@ -121,15 +247,10 @@ impl Config {
       builder.overrides(ob);
       builder.standard_filters(true);
       let walker = builder.build();
-      for entry in walker {
-        if let Ok(entry) = entry {
-          let is_file = entry
-            .file_type()
-            .and_then(|f| Some(f.is_file()))
-            .unwrap_or(false);
-          if is_file {
-            self.files.push(entry.path().to_path_buf())
-          }
+      for entry in walker.flatten() {
+        let is_file = entry.file_type().map(|f| f.is_file()).unwrap_or(false);
+        if is_file {
+          self.files.push(entry.path().to_path_buf())
         }
       }
     };
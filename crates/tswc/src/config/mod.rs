@@ -1,2 +1,5 @@
+#[allow(clippy::module_inception)]
 mod config;
-pub use config::{Config, ConfigOptions};
+pub use config::{
+  BarrelSideEffects, BoundaryRule, Config, ConfigOptions, ModularizeImport, WorkspaceStrategy,
+};
@@ -1,3 +1,4 @@
+mod analysis;
 pub mod apis;
 mod compiler;
 mod config;
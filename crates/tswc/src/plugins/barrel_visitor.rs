@@ -158,6 +158,13 @@ impl<'a> Fold for Barrel<'a> {
       match item {
         ModuleItem::ModuleDecl(decl) => {
           match decl {
+            // import './setup' is skipped once imports are rewritten
+            ModuleDecl::Import(import_decl) if import_decl.specifiers.is_empty() => {
+              is_barrel = false;
+              if !self.wildcard {
+                break;
+              }
+            }
             ModuleDecl::Import(_) => {}
             // export { foo } from './foo';
             ModuleDecl::ExportNamed(export_named) => {
@@ -201,8 +208,15 @@ impl<'a> Fold for Barrel<'a> {
                       break;
                     }
                   }
-                  _ => {
-                    if !self.wildcard {
+                  // export v from './v'
+                  ExportSpecifier::Default(s) => {
+                    if let Some(src) = &export_named.src {
+                      export_map.push((
+                        s.exported.sym.to_string(),
+                        src.value.to_string(),
+                        "default".to_string(),
+                      ));
+                    } else if !self.wildcard {
                       is_barrel = false;
                       break;
                     }
@@ -217,8 +231,8 @@ impl<'a> Fold for Barrel<'a> {
             // export function foo() {}
             ModuleDecl::ExportDecl(export_decl) => {
               // Export declarations are not allowed in barrel files.
+              is_barrel = false;
               if !self.wildcard {
-                is_barrel = false;
                 break;
               }
 
@@ -239,9 +253,9 @@ impl<'a> Fold for Barrel<'a> {
               }
             }
             _ => {
+              // Other expressions are not allowed in barrel files.
+              is_barrel = false;
               if !self.wildcard {
-                // Other expressions are not allowed in barrel files.
-                is_barrel = false;
                 break;
               }
             }
@@ -251,15 +265,15 @@ impl<'a> Fold for Barrel<'a> {
           Stmt::Expr(expr) => match &*expr.expr {
             Expr::Lit(_) => {}
             _ => {
+              is_barrel = false;
               if !self.wildcard {
-                is_barrel = false;
                 break;
               }
             }
           },
           _ => {
+            is_barrel = false;
             if !self.wildcard {
-              is_barrel = false;
               break;
             }
           }
@@ -275,6 +289,9 @@ impl<'a> Fold for Barrel<'a> {
     self
       .module_graph
       .set_exports_info(&self.context, export_map, export_wildcards);
+    if let Some(m) = self.module_graph.modules.get_mut(&self.context) {
+      m.is_barrel = is_barrel;
+    }
     items
   }
 }
@@ -1,13 +1,20 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use log::debug;
+use swc_core::common::util::take::Take;
+use swc_core::common::{BytePos, SourceMap, SourceMapper, Span, Spanned, DUMMY_SP};
 use swc_core::ecma::ast::{
   self, ImportPhase, ImportSpecifier as SWCImportSpecifier, ModuleExportName,
 };
-use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use swc_core::ecma::visit::{Fold, VisitMut, VisitMutWith};
 
-use crate::compiler::{ModuleGraph, ResolveModuleOptions};
-use crate::utils::{ExportSpecifier, ImportSpecifier, ImportType};
+use super::{NamedImportTransform, NamedImportTransformConfig};
+use crate::analysis::check_boundaries;
+use crate::compiler::{Edge, EdgeKind, Location, ModuleGraph, ResolveModuleOptions};
+use crate::utils::{
+  to_module_export_name, ExportKind, ExportSpecifier, ImportSpecifier, ImportType,
+};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -25,17 +32,30 @@ lazy_static! {
 
 pub struct ImportExportVisitor<'a> {
   pub context: String,
+  pub cm: Arc<SourceMap>,
+  /// Span of the module declaration or dynamic import being visited
+  pub span: Span,
   pub imports: Vec<ImportSpecifier>,
-  pub module_graph: &'a mut ModuleGraph,
+  /// Imports are resolved and rewritten only if module graph is provided
+  pub module_graph: Option<&'a mut ModuleGraph>,
   pub exports: Vec<ExportSpecifier>,
   pub facade: bool,
   pub local_idents: HashMap<String, (String, String)>,
+  /// Kinds of top level declarations and namespace imports
+  pub local_kinds: HashMap<String, ExportKind>,
+  declarations_detected: bool,
   pub has_module_syntax: bool,
 }
 
 impl<'a> ImportExportVisitor<'a> {
-  pub fn new(module_graph: &'a mut ModuleGraph, context: String) -> Self {
+  pub fn new(
+    module_graph: Option<&'a mut ModuleGraph>,
+    context: String,
+    cm: Arc<SourceMap>,
+  ) -> Self {
     Self {
+      cm,
+      span: DUMMY_SP,
       imports: vec![],
       exports: vec![],
       facade: false,
@@ -43,41 +63,179 @@ impl<'a> ImportExportVisitor<'a> {
       module_graph,
       context,
       local_idents: HashMap::default(),
+      local_kinds: HashMap::default(),
+      declarations_detected: false,
+    }
+  }
+}
+
+/// Sources of value imports and re-exports with bindings
+fn module_sources(module: &ast::Module) -> Vec<String> {
+  let mut sources = vec![];
+  for item in &module.body {
+    let src = match item {
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(decl))
+        if !decl.type_only
+          && decl
+            .specifiers
+            .iter()
+            .any(|f| !matches!(f, SWCImportSpecifier::Named(named) if named.is_type_only)) =>
+      {
+        &decl.src
+      }
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(export))
+        if !export.type_only
+          && export
+            .specifiers
+            .iter()
+            .any(|f| !matches!(f, ast::ExportSpecifier::Named(named) if named.is_type_only)) =>
+      {
+        match &export.src {
+          Some(src) => src,
+          None => continue,
+        }
+      }
+      _ => continue,
+    };
+    let src = src.value.to_string();
+    if !sources.contains(&src) {
+      sources.push(src);
     }
   }
+  sources
 }
 
 // import
 impl<'a> ImportExportVisitor<'a> {
-  fn add_import(&mut self, import: ImportSpecifier) -> Option<String> {
-    let src = import.src.clone();
-    let spec = import.n.clone();
-    debug!(target: "tswc", "add import {:?} {:?}", src, self.context);
-    self.imports.push(import);
+  /// Split imports and re-exports of local barrels like barrel packages, so they can be rewritten to
+  /// the underlying modules. Typescript is stripped already, imports only used as types are gone
+  fn split_local_barrels(&mut self, module: &mut ast::Module) {
+    let Some(module_graph) = self.module_graph.as_deref_mut() else {
+      return;
+    };
+    let packages: Vec<String> = module_sources(module)
+      .into_iter()
+      .filter(|src| {
+        !module_graph
+          .config
+          .resolved_options
+          .barrel_packages
+          .contains(src)
+          && module_graph
+            .resolve_local_barrel(src, &self.context)
+            .is_some()
+      })
+      .collect();
+    if packages.is_empty() {
+      return;
+    }
+    let mut transform = NamedImportTransform::new(NamedImportTransformConfig { packages });
+    *module = transform.fold_module(module.take());
+  }
 
+  /// Record edge from current module to resolved (id, rewritten) into module graph
+  fn add_edge(
+    &mut self,
+    src: Option<String>,
+    resolved: &Option<(String, String)>,
+    kind: Option<EdgeKind>,
+    names: Vec<String>,
+    is_rewritten: bool,
+  ) {
+    let Some(module_graph) = self.module_graph.as_deref_mut() else {
+      return;
+    };
+    if let (Some(specifier), Some((to, rewritten)), Some(kind)) = (src, resolved, kind) {
+      let loc = (!self.span.is_dummy()).then(|| {
+        let loc = self.cm.lookup_char_pos(self.span.lo);
+        Location {
+          line: loc.line,
+          column: loc.col_display + 1,
+        }
+      });
+      let edge = Edge {
+        from: self.context.clone(),
+        to: to.clone(),
+        kind,
+        specifier,
+        names,
+        rewritten: is_rewritten.then(|| rewritten.clone()),
+        loc,
+      };
+      // Re-exports are checked as well, rules can't be bypassed by `export { a } from 'b'`.
+      // Merged edges were checked when first added
+      if module_graph.add_edge(edge) {
+        let edge = module_graph.edges.last().expect("edge is added");
+        let diagnostics = check_boundaries(module_graph, edge);
+        module_graph.diagnostics.extend(diagnostics);
+      }
+    }
+  }
+
+  /// Resolve src into (module id, specifier in output, name exported by the module), name is only
+  /// returned if `specifier` of barrel package is resolved to the module declares it
+  fn resolve(
+    &mut self,
+    src: &Option<String>,
+    specifier: Option<String>,
+  ) -> Option<(String, String, Option<String>)> {
+    let module_graph = self.module_graph.as_deref_mut()?;
     let options = ResolveModuleOptions {
       src: src.clone(),
       context: self.context.clone(),
-      specifier: spec.clone(),
+      specifier,
       ..Default::default()
     };
-    let gm = self.module_graph.get_module(options);
-    if let Some(m) = gm {
-      return Some(m.v_abs_path.clone());
-    } else {
-      let options = ResolveModuleOptions {
-        src: src.clone(),
-        context: self.context.clone(),
-        specifier: spec.clone(),
-        ..Default::default()
-      };
-      let context = self.module_graph.resolve_context(&self.context);
-      let rm = self.module_graph.resolve_module(options);
-      if let Some(m) = rm {
-        return Some(m.with_ext(&context).clone());
+    let context = module_graph.resolve_context(&self.context);
+    let barrel = module_graph.get_module(options).map(|(m, orig)| {
+      let rewritten = (!m.is_node_modules).then(|| m.with_ext(&m.abs_path, &context));
+      (m.id(), rewritten, orig)
+    });
+    if let Some((id, rewritten, orig)) = barrel {
+      // Filepath inside node_modules is unusable in output, use public subpath of the package instead
+      let rewritten = rewritten.or_else(|| module_graph.public_specifier(&id));
+      match rewritten {
+        Some(rewritten) => return Some((id, rewritten, Some(orig))),
+        None => debug!(target: "tswc", "{:?} is not exported by its package", id),
       }
-      return None;
     }
+    let options = ResolveModuleOptions {
+      src: src.clone(),
+      context: self.context.clone(),
+      ..Default::default()
+    };
+    let rm = module_graph.resolve_module(options);
+    rm.map(|m| {
+      (
+        m.id(),
+        m.with_ext(src.as_deref().unwrap_or_default(), &context),
+        None,
+      )
+    })
+  }
+
+  /// Returns (specifier in output, name exported by resolved module if it is barrel optimized)
+  fn add_import(
+    &mut self,
+    import: ImportSpecifier,
+    names: Vec<String>,
+  ) -> Option<(String, Option<String>)> {
+    let src = import.src.clone();
+    let spec = import.n.clone();
+    let kind = EdgeKind::from_import_type(import.t);
+    debug!(target: "tswc", "add import {:?} {:?}", src, self.context);
+    self.imports.push(import);
+
+    // Imports of barrel packages are split into single specifier by NamedImportTransform
+    let specifier = match names.as_slice() {
+      [name] if spec.is_some() => Some(name.clone()),
+      _ => None,
+    };
+    let (id, rewritten, orig) = self.resolve(&src, specifier)?;
+    let resolved = Some((id, rewritten.clone()));
+    // Only specifiers of import with bindings are rewritten, e.g. `import './a'` and `import('./a')` are not
+    self.add_edge(src, &resolved, kind, names, spec.is_some());
+    Some((rewritten, orig))
   }
 
   fn parse_import(&mut self, import: &mut ast::ImportDecl) {
@@ -90,11 +248,18 @@ impl<'a> ImportExportVisitor<'a> {
     // import 'b'
     if import.specifiers.is_empty() {
       let name = import.src.value.to_string();
-      self.add_import(ImportSpecifier {
-        src: Some(name),
-        t: ImportType::Static,
-        n: None,
-      });
+      self.add_import(
+        ImportSpecifier {
+          src: Some(name),
+          t: ImportType::Static,
+          n: None,
+          src_span: Some(import.src.span),
+          statement_span: self.span,
+          dynamic: None,
+          attributes: import.with.as_ref().map(|f| f.span),
+        },
+        vec![],
+      );
       return;
     }
 
@@ -168,13 +333,36 @@ impl<'a> ImportExportVisitor<'a> {
 
         // Replace src with resolver's result
         if t.is_some() {
-          let m = self.add_import(ImportSpecifier {
-            src: Some(name),
-            t: t.unwrap(),
-            n: Some(first_specifier.local().sym.to_string()),
-          });
-          if let Some(v) = m {
+          let names = import
+            .specifiers
+            .iter()
+            .filter_map(|spec| match spec {
+              SWCImportSpecifier::Named(s) if s.is_type_only => None,
+              SWCImportSpecifier::Named(s) => self
+                .local_idents
+                .get(&s.local.sym.to_string())
+                .map(|(_, imported)| imported.clone()),
+              SWCImportSpecifier::Namespace(_) => Some("*".to_string()),
+              SWCImportSpecifier::Default(_) => Some(DEFAULT_EXPORT.to_string()),
+            })
+            .collect();
+          let m = self.add_import(
+            ImportSpecifier {
+              src: Some(name),
+              t: t.unwrap(),
+              n: Some(first_specifier.local().sym.to_string()),
+              src_span: Some(import.src.span),
+              statement_span: self.span,
+              dynamic: None,
+              attributes: import.with.as_ref().map(|f| f.span),
+            },
+            names,
+          );
+          if let Some((v, orig)) = m {
             import.src = Box::new(ast::Str::from(v));
+            if let (Some(orig), [specifier]) = (orig, import.specifiers.as_mut_slice()) {
+              rename_import_specifier(specifier, &orig);
+            }
           }
         }
       }
@@ -184,38 +372,39 @@ impl<'a> ImportExportVisitor<'a> {
 
 // export
 impl<'a> ImportExportVisitor<'a> {
-  fn add_export(&mut self, export: ExportSpecifier) -> Option<String> {
+  /// Returns (specifier in output, name exported by resolved module if it is barrel optimized)
+  fn add_export(&mut self, mut export: ExportSpecifier) -> Option<(String, Option<String>)> {
     let src = export.src.clone();
-    self.exports.push(export);
-    let options = ResolveModuleOptions {
-      src: src.clone(),
-      context: self.context.clone(),
-      ..Default::default()
-    };
-    let gm = self.module_graph.get_module(options);
-    if let Some(m) = gm {
-      return Some(m.v_abs_path.clone());
-    } else {
-      let options = ResolveModuleOptions {
-        src: src.clone(),
-        context: self.context.clone(),
-        ..Default::default()
-      };
-      let context = self.module_graph.resolve_context(&self.context);
-      let rm = self.module_graph.resolve_module(options);
-      if let Some(m) = rm {
-        return Some(m.with_ext(&context).clone());
+    // Exports of import bindings, use imported name of src as ln
+    if let Some(ln) = &export.ln {
+      if let Some((local_src, imported)) = self.local_idents.get(ln) {
+        if Some(local_src) == src.as_ref() {
+          export.ln = Some(imported.clone());
+        }
       }
-      return None;
     }
+    let name = match &export.ln {
+      Some(ln) if !ln.is_empty() => ln.clone(),
+      _ => "*".to_string(),
+    };
+    self.exports.push(export);
+    // Re-exports of barrel packages are split into single specifier by NamedImportTransform
+    let specifier = (src.is_some() && name != "*").then(|| name.clone());
+    let (id, rewritten, orig) = self.resolve(&src, specifier)?;
+    let resolved = Some((id, rewritten.clone()));
+    self.add_edge(src, &resolved, Some(EdgeKind::ReExport), vec![name], true);
+    Some((rewritten, orig))
   }
 
-  fn add_export_from_ident(&mut self, ident: &ast::Ident) {
+  fn add_export_from_ident(&mut self, ident: &ast::Ident, kind: ExportKind) {
     let name = ident.sym.to_string();
     self.add_export(ExportSpecifier {
       n: name.clone(),
       ln: Some(name),
       src: None,
+      span: Some(ident.span),
+      local_span: Some(ident.span),
+      kind,
     });
   }
 
@@ -223,7 +412,7 @@ impl<'a> ImportExportVisitor<'a> {
     &mut self,
     specifier: &ast::ExportSpecifier,
     export_named: &mut ast::NamedExport,
-  ) -> (bool, Option<String>) {
+  ) -> (bool, Option<(String, Option<String>)>) {
     match specifier {
       ast::ExportSpecifier::Named(named) => {
         // skip type
@@ -272,37 +461,56 @@ impl<'a> ImportExportVisitor<'a> {
           None
         };
 
+        let kind = match &export_named.src {
+          // Resolved through module graph
+          Some(_) => ExportKind::Value,
+          None => self.local_kind(origin_name.as_deref().unwrap_or_default()),
+        };
+        // `export { A }` of interface, recorded by detect_declarations
+        if kind == ExportKind::Type {
+          return (false, None);
+        }
         debug!(target: "tswc", "add export {:?} {:?}", src_str, self.context);
         let m = self.add_export(ExportSpecifier {
           n: name,
           ln: origin_name,
           src: src_str,
+          span: Some(named.exported.as_ref().unwrap_or(&named.orig).span()),
+          // `export { a } from 'b'` has no local binding
+          local_span: export_named.src.is_none().then(|| named.orig.span()),
+          kind,
         });
 
-        return (true, m);
+        (true, m)
       }
       // export v from 'm'
-      // current not support
-      ast::ExportSpecifier::Default(_) => {
-        return (false, None);
+      ast::ExportSpecifier::Default(default) => {
+        let m = self.add_export(ExportSpecifier {
+          n: default.exported.sym.to_string(),
+          ln: Some(DEFAULT_EXPORT.to_string()),
+          src: export_named.src.as_ref().map(|f| f.value.to_string()),
+          span: Some(default.exported.span),
+          local_span: None,
+          kind: ExportKind::Value,
+        });
+        (true, m)
       }
       // export * as a from 'b'
       ast::ExportSpecifier::Namespace(namespace) => {
         if let ast::ModuleExportName::Ident(ident) = &namespace.name {
           let name = ident.sym.to_string();
-          let src_str: Option<String> = if let Some(src) = &export_named.src {
-            Some(src.value.to_string())
-          } else {
-            None
-          };
+          let src_str: Option<String> = export_named.src.as_ref().map(|src| src.value.to_string());
           let m = self.add_export(ExportSpecifier {
             n: name,
             ln: None,
             src: src_str,
+            span: Some(ident.span),
+            local_span: None,
+            kind: ExportKind::Namespace,
           });
           return (true, m);
         }
-        return (false, None);
+        (false, None)
       }
     }
   }
@@ -323,8 +531,12 @@ impl<'a> ImportExportVisitor<'a> {
       return false;
     }
 
+    if let Some(src) = &export.src {
+      let src = src.clone();
+      self.add_reexport_import(&src, export.with.as_ref().map(|f| f.span));
+    }
     let mut is_need_add_import = false;
-    let mut resolved_src: Option<String> = None;
+    let mut resolved_src: Option<(String, Option<String>)> = None;
     let specifiers = &export.specifiers.clone();
     for specifier in specifiers {
       let (need_add_import, m) = self.parse_export_spec(specifier, export);
@@ -336,36 +548,55 @@ impl<'a> ImportExportVisitor<'a> {
       }
     }
 
-    if let Some(v) = resolved_src {
+    if let Some((v, orig)) = resolved_src {
       export.src = Some(Box::new(ast::Str::from(v)));
+      if let (Some(orig), [specifier]) = (orig, export.specifiers.as_mut_slice()) {
+        rename_export_specifier(specifier, &orig);
+      }
     }
-    return is_need_add_import;
+    is_need_add_import
   }
 
-  fn parse_default_export_expr(&mut self, _export: &ast::ExportDefaultExpr) {
+  fn parse_default_export_expr(&mut self, export: &ast::ExportDefaultExpr) {
     let name = DEFAULT_EXPORT.to_string();
+    let kind = match export.expr.as_ref() {
+      ast::Expr::Ident(ident) => self.local_kind(&ident.sym),
+      _ => ExportKind::Value,
+    };
     self.add_export(ExportSpecifier {
       n: name,
       ln: None,
       src: None,
+      span: self.default_keyword_span(export.span),
+      local_span: None,
+      kind,
     });
   }
 
   fn parse_export_decl(&mut self, export: &ast::ExportDecl) -> bool {
     let mut need_eager_return = false;
     match &export.decl {
-      ast::Decl::Class(decl) => self.add_export_from_ident(&decl.ident),
-      ast::Decl::Fn(decl) => self.add_export_from_ident(&decl.ident),
+      ast::Decl::Class(decl) => self.add_export_from_ident(&decl.ident, ExportKind::Class),
+      ast::Decl::Fn(decl) => self.add_export_from_ident(&decl.ident, ExportKind::Function),
       ast::Decl::Var(decl) => {
         decl.decls.iter().for_each(|decl| {
           // support export const a = 1, b = 2
           match &decl.name {
             ast::Pat::Ident(ident) => {
               let name = ident.sym.to_string();
+              // Enums and namespaces are declared by `var` after typescript is stripped
+              let kind = self
+                .local_kinds
+                .get(&name)
+                .copied()
+                .unwrap_or(ExportKind::Const);
               self.add_export(ExportSpecifier {
                 n: name.clone(),
                 ln: Some(name),
                 src: None,
+                span: Some(ident.span),
+                local_span: Some(ident.span),
+                kind,
               });
             }
             ast::Pat::Object(pat) => {
@@ -375,10 +606,19 @@ impl<'a> ImportExportVisitor<'a> {
                   ast::ObjectPatProp::Assign(assign) => {
                     let ident = &assign.key;
                     let name = ident.sym.to_string();
+                    // Enums and namespaces are declared by `var` after typescript is stripped
+                    let kind = self
+                      .local_kinds
+                      .get(&name)
+                      .copied()
+                      .unwrap_or(ExportKind::Const);
                     self.add_export(ExportSpecifier {
                       n: name.clone(),
                       ln: Some(name),
                       src: None,
+                      span: Some(ident.span),
+                      local_span: Some(ident.span),
+                      kind,
                     });
                   }
                   ast::ObjectPatProp::KeyValue(kv) => {
@@ -387,10 +627,19 @@ impl<'a> ImportExportVisitor<'a> {
                       ast::Pat::Ident(ident) => {
                         // only support value is ident
                         let name = ident.sym.to_string();
+                        // Enums and namespaces are declared by `var` after typescript is stripped
+                        let kind = self
+                          .local_kinds
+                          .get(&name)
+                          .copied()
+                          .unwrap_or(ExportKind::Const);
                         self.add_export(ExportSpecifier {
                           n: name.clone(),
                           ln: Some(name),
                           src: None,
+                          span: Some(ident.span),
+                          local_span: Some(ident.span),
+                          kind,
                         });
                       }
                       _ => {
@@ -410,10 +659,19 @@ impl<'a> ImportExportVisitor<'a> {
                   // only support export const [a, b] = []
                   if let ast::Pat::Ident(ident) = &elm.as_ref().unwrap() {
                     let name = ident.sym.to_string();
+                    // Enums and namespaces are declared by `var` after typescript is stripped
+                    let kind = self
+                      .local_kinds
+                      .get(&name)
+                      .copied()
+                      .unwrap_or(ExportKind::Const);
                     self.add_export(ExportSpecifier {
                       n: name.clone(),
                       ln: Some(name),
                       src: None,
+                      span: Some(ident.span),
+                      local_span: Some(ident.span),
+                      kind,
                     });
                   }
                 }
@@ -430,6 +688,9 @@ impl<'a> ImportExportVisitor<'a> {
           n: name.clone(),
           ln: Some(name),
           src: None,
+          span: Some(decl.id.span),
+          local_span: Some(decl.id.span),
+          kind: ExportKind::Enum,
         });
       }
       ast::Decl::TsModule(decl) => {
@@ -439,6 +700,9 @@ impl<'a> ImportExportVisitor<'a> {
             n: name.clone(),
             ln: Some(name),
             src: None,
+            span: Some(ident.span),
+            local_span: Some(ident.span),
+            kind: ExportKind::Namespace,
           });
         }
         // do not visit import / export within namespace
@@ -461,6 +725,9 @@ impl<'a> ImportExportVisitor<'a> {
             n: DEFAULT_EXPORT.to_string(),
             ln: Some(origin_name),
             src: None,
+            span: self.default_keyword_span(export.span),
+            local_span: Some(ident.span),
+            kind: ExportKind::Class,
           });
         } else {
           let name = DEFAULT_EXPORT.to_string();
@@ -468,6 +735,9 @@ impl<'a> ImportExportVisitor<'a> {
             n: name,
             ln: None,
             src: None,
+            span: self.default_keyword_span(export.span),
+            local_span: None,
+            kind: ExportKind::Class,
           });
         }
       }
@@ -480,6 +750,9 @@ impl<'a> ImportExportVisitor<'a> {
             n: DEFAULT_EXPORT.to_string(),
             ln: Some(origin_name),
             src: None,
+            span: self.default_keyword_span(export.span),
+            local_span: Some(ident.span),
+            kind: ExportKind::Function,
           });
         } else {
           let name = DEFAULT_EXPORT.to_string();
@@ -487,6 +760,9 @@ impl<'a> ImportExportVisitor<'a> {
             n: name.clone(),
             ln: None,
             src: None,
+            span: self.default_keyword_span(export.span),
+            local_span: None,
+            kind: ExportKind::Function,
           });
         }
       }
@@ -495,8 +771,203 @@ impl<'a> ImportExportVisitor<'a> {
   }
 }
 
+/// Import `orig` of barrel optimized module with the same local binding
+/// e.g. `import { Button }` -> `import Button` if Button is re-exported default
+fn rename_import_specifier(specifier: &mut SWCImportSpecifier, orig: &str) {
+  let local = specifier.local().clone();
+  let span = specifier.span();
+  *specifier = match orig {
+    "default" => SWCImportSpecifier::Default(ast::ImportDefaultSpecifier { span, local }),
+    "*" => SWCImportSpecifier::Namespace(ast::ImportStarAsSpecifier { span, local }),
+    _ => SWCImportSpecifier::Named(ast::ImportNamedSpecifier {
+      span,
+      imported: (local.sym != orig).then(|| to_module_export_name(orig)),
+      local,
+      is_type_only: false,
+    }),
+  };
+}
+
+/// Re-export `orig` of barrel optimized module with the same exported name
+/// e.g. `export { Button } from` -> `export { default as Button } from` if Button is re-exported default
+fn rename_export_specifier(specifier: &mut ast::ExportSpecifier, orig: &str) {
+  let span = specifier.span();
+  let exported = match specifier {
+    ast::ExportSpecifier::Named(named) => named.exported.clone().unwrap_or(named.orig.clone()),
+    ast::ExportSpecifier::Default(default) => ModuleExportName::Ident(default.exported.clone()),
+    ast::ExportSpecifier::Namespace(namespace) => namespace.name.clone(),
+  };
+  if orig == "*" {
+    *specifier = ast::ExportSpecifier::Namespace(ast::ExportNamespaceSpecifier {
+      span,
+      name: exported,
+    });
+    return;
+  }
+  let orig = to_module_export_name(orig);
+  let is_renamed = module_export_name(&orig) != module_export_name(&exported);
+  *specifier = ast::ExportSpecifier::Named(ast::ExportNamedSpecifier {
+    span,
+    orig,
+    exported: is_renamed.then_some(exported),
+    is_type_only: false,
+  });
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
+
 // utils
 impl<'a> ImportExportVisitor<'a> {
+  /// Kind of top level declaration or import binding, see detect_declarations
+  fn local_kind(&self, name: &str) -> ExportKind {
+    self.local_kinds.get(name).copied().unwrap_or_default()
+  }
+
+  /// Type only exports are erased from output, so they are recorded without resolving
+  fn add_type_export_from_ident(&mut self, name: &str, ident: &ast::Ident, span: Option<Span>) {
+    self.exports.push(ExportSpecifier {
+      n: name.to_string(),
+      ln: Some(ident.sym.to_string()),
+      src: None,
+      span,
+      local_span: Some(ident.span),
+      kind: ExportKind::Type,
+    });
+  }
+
+  fn add_type_export(
+    &mut self,
+    named: &ast::ExportNamedSpecifier,
+    export_named: &ast::NamedExport,
+  ) {
+    let orig = module_export_name(&named.orig);
+    self.exports.push(ExportSpecifier {
+      n: named
+        .exported
+        .as_ref()
+        .map(module_export_name)
+        .unwrap_or_else(|| orig.clone()),
+      ln: Some(orig),
+      src: export_named.src.as_ref().map(|f| f.value.to_string()),
+      span: Some(named.exported.as_ref().unwrap_or(&named.orig).span()),
+      local_span: export_named.src.is_none().then(|| named.orig.span()),
+      kind: ExportKind::Type,
+    });
+  }
+
+  /// Detect kinds of top level declarations and record type only exports, should be called with
+  /// module before typescript is stripped, otherwise it is called by visit_mut_module
+  pub fn detect_declarations(&mut self, module: &ast::Module) {
+    if self.declarations_detected {
+      return;
+    }
+    self.declarations_detected = true;
+    self.detect_local_kinds(module);
+    for item in module.body.iter() {
+      match item {
+        ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDecl(export)) => match &export.decl {
+          ast::Decl::TsInterface(decl) => {
+            self.add_type_export_from_ident(&decl.id.sym, &decl.id, Some(decl.id.span))
+          }
+          ast::Decl::TsTypeAlias(decl) => {
+            self.add_type_export_from_ident(&decl.id.sym, &decl.id, Some(decl.id.span))
+          }
+          _ => {}
+        },
+        // export type { a } from 'b'
+        // export { type a, b }
+        ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(export)) => {
+          for specifier in &export.specifiers {
+            let ast::ExportSpecifier::Named(named) = specifier else {
+              continue;
+            };
+            let is_local_type = export.src.is_none()
+              && self.local_kind(&module_export_name(&named.orig)) == ExportKind::Type;
+            if export.type_only || named.is_type_only || is_local_type {
+              self.add_type_export(named, export);
+            }
+          }
+        }
+        // export default interface A {}
+        ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultDecl(export)) => {
+          if let ast::DefaultDecl::TsInterfaceDecl(decl) = &export.decl {
+            let span = self.default_keyword_span(export.span);
+            self.add_type_export_from_ident(*DEFAULT_EXPORT, &decl.id, span);
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn detect_local_kinds(&mut self, module: &ast::Module) {
+    for item in module.body.iter() {
+      let decl = match item {
+        ast::ModuleItem::Stmt(ast::Stmt::Decl(decl)) => decl,
+        ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDecl(export)) => &export.decl,
+        ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(import)) => {
+          for specifier in &import.specifiers {
+            if let SWCImportSpecifier::Namespace(s) = specifier {
+              self
+                .local_kinds
+                .insert(s.local.sym.to_string(), ExportKind::Namespace);
+            }
+          }
+          continue;
+        }
+        _ => continue,
+      };
+      let mut insert = |ident: &ast::Ident, kind: ExportKind| {
+        self.local_kinds.insert(ident.sym.to_string(), kind);
+      };
+      match decl {
+        ast::Decl::Class(decl) => insert(&decl.ident, ExportKind::Class),
+        ast::Decl::Fn(decl) => insert(&decl.ident, ExportKind::Function),
+        ast::Decl::Var(decl) => {
+          for decl in &decl.decls {
+            if let ast::Pat::Ident(ident) = &decl.name {
+              insert(&ident.id, ExportKind::Const);
+            }
+          }
+        }
+        ast::Decl::TsEnum(decl) => insert(&decl.id, ExportKind::Enum),
+        ast::Decl::TsModule(decl) => {
+          if let ast::TsModuleName::Ident(ident) = &decl.id {
+            insert(ident, ExportKind::Namespace);
+          }
+        }
+        ast::Decl::TsInterface(decl) => insert(&decl.id, ExportKind::Type),
+        ast::Decl::TsTypeAlias(decl) => insert(&decl.id, ExportKind::Type),
+        ast::Decl::Using(_) => {}
+      }
+    }
+  }
+
+  /// Span of `default` keyword in `export default ...`
+  fn default_keyword_span(&self, span: Span) -> Option<Span> {
+    let snippet = self.cm.span_to_snippet(span).ok()?;
+    let lo = span.lo + BytePos(snippet.find(*DEFAULT_EXPORT)? as u32);
+    Some(Span::new(lo, lo + BytePos(*DEFAULT_EXPORT_LEN as u32)))
+  }
+
+  /// `export ... from 'b'` imports `b` as es-module-lexer does, edges are recorded by add_export
+  fn add_reexport_import(&mut self, src: &ast::Str, attributes: Option<Span>) {
+    self.imports.push(ImportSpecifier {
+      n: None,
+      src: Some(src.value.to_string()),
+      t: ImportType::Static,
+      src_span: Some(src.span),
+      statement_span: self.span,
+      dynamic: None,
+      attributes,
+    });
+  }
+
   fn detect_facade(&mut self, module: &mut ast::Module) {
     let mut is_facade = true;
     for item in module.body.iter() {
@@ -564,13 +1035,9 @@ impl<'a> ImportExportVisitor<'a> {
           if let ast::Stmt::Expr(expr) = stmt {
             if let ast::Expr::Call(call) = expr.expr.as_ref() {
               let is_dynamic_import = call.callee.is_import();
-              if is_dynamic_import {
-                if call.args.len() == 1 {
-                  if let ast::Expr::Lit(lit) = call.args[0].expr.as_ref() {
-                    if let ast::Lit::Str(_) = lit {
-                      continue;
-                    }
-                  }
+              if is_dynamic_import && call.args.len() == 1 {
+                if let ast::Expr::Lit(ast::Lit::Str(_)) = call.args[0].expr.as_ref() {
+                  continue;
                 }
               }
             }
@@ -603,13 +1070,19 @@ impl<'a> ImportExportVisitor<'a> {
 // visit
 impl<'a> VisitMut for ImportExportVisitor<'a> {
   fn visit_mut_module(&mut self, module: &mut ast::Module) {
+    self.split_local_barrels(module);
     self.detect_facade(module);
     self.detect_syntax(module);
+    self.detect_declarations(module);
     module.visit_mut_children_with(self);
+    if let Some(module_graph) = self.module_graph.as_deref_mut() {
+      module_graph.set_module_specifiers(&self.context, self.imports.clone(), self.exports.clone());
+    }
   }
 
   // normal
   fn visit_mut_module_decl(&mut self, decl: &mut ast::ModuleDecl) {
+    self.span = decl.span();
     match decl {
       // import
       ast::ModuleDecl::Import(import) => {
@@ -648,12 +1121,14 @@ impl<'a> VisitMut for ImportExportVisitor<'a> {
       ast::ModuleDecl::ExportAll(export) => {
         // add import
         let name = export.src.value.to_string();
+        self.add_reexport_import(&export.src, export.with.as_ref().map(|f| f.span));
         let m = self.add_export(ExportSpecifier {
           n: "*".into(),
           ln: Some("".into()),
           src: Some(name),
+          ..Default::default()
         });
-        if let Some(v) = m {
+        if let Some((v, _)) = m {
           export.src = Box::new(ast::Str::from(v));
         }
       }
@@ -676,17 +1151,16 @@ impl<'a> VisitMut for ImportExportVisitor<'a> {
   fn visit_mut_expr(&mut self, node: &mut ast::Expr) {
     if let ast::Expr::Call(call) = node {
       if let ast::Callee::Import(import) = call.callee {
-        let first_arg = call.args.get(0);
+        self.span = call.span;
+        let first_arg = call.args.first();
         if let Some(arg) = first_arg {
           let mut name = None;
 
           // get static value
           match arg.expr.as_ref() {
             // import('abc')
-            ast::Expr::Lit(lit) => {
-              if let ast::Lit::Str(src) = lit {
-                name = Some(src.value.to_string());
-              }
+            ast::Expr::Lit(ast::Lit::Str(src)) => {
+              name = Some(src.value.to_string());
             }
             // import(`abc`)
             ast::Expr::Tpl(_tpl) => {
@@ -720,12 +1194,19 @@ impl<'a> VisitMut for ImportExportVisitor<'a> {
             }
           }
 
-          if t.is_some() {
-            self.add_import(ImportSpecifier {
-              src: name,
-              t: t.unwrap(),
-              n: None,
-            });
+          if let Some(t) = t {
+            self.add_import(
+              ImportSpecifier {
+                src: name,
+                t,
+                n: None,
+                src_span: Some(arg.expr.span()),
+                statement_span: call.span,
+                dynamic: Some(import.span.hi),
+                attributes: second_arg.map(|f| f.expr.span()),
+              },
+              vec![],
+            );
           }
         }
       }
@@ -747,11 +1228,18 @@ impl<'a> VisitMut for ImportExportVisitor<'a> {
   // import.meta.xxx
   // import.meta
   fn visit_mut_meta_prop_expr(&mut self, meta: &mut ast::MetaPropExpr) {
-    self.add_import(ImportSpecifier {
-      src: None,
-      t: ImportType::ImportMeta,
-      n: None,
-    });
+    self.add_import(
+      ImportSpecifier {
+        src: None,
+        t: ImportType::ImportMeta,
+        n: None,
+        src_span: None,
+        statement_span: meta.span,
+        dynamic: None,
+        attributes: None,
+      },
+      vec![],
+    );
     // `import.meta` can only appear in module
     self.set_module_syntax(true);
     meta.visit_mut_children_with(self);
@@ -1,8 +1,12 @@
 mod barrel_visitor;
 mod export_import_visitor;
+mod modularize_imports_visitor;
 mod named_import_transform_visitor;
 pub use barrel_visitor::{Barrel, Config as BarrelConfig};
 pub use export_import_visitor::ImportExportVisitor;
+pub use modularize_imports_visitor::{
+  validate_template, Config as ModularizeImportsConfig, ModularizeImports,
+};
 pub use named_import_transform_visitor::{
   Config as NamedImportTransformConfig, NamedImportTransform,
 };
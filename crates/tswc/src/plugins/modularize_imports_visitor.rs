@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use log::debug;
+use swc_core::common::DUMMY_SP;
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{noop_fold_type, Fold};
+
+use crate::config::ModularizeImport;
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+  pub packages: HashMap<String, ModularizeImport>,
+}
+
+#[derive(Debug, Default)]
+pub struct ModularizeImports {
+  pub packages: HashMap<String, ModularizeImport>,
+}
+
+impl ModularizeImports {
+  pub fn new(config: Config) -> Self {
+    ModularizeImports {
+      packages: config.packages,
+    }
+  }
+}
+
+/// Words of identifier, e.g. `ArrowLeft2` -> [`Arrow`, `Left2`], `arrow_left` -> [`arrow`, `left`]
+fn split_words(member: &str) -> Vec<String> {
+  let mut words: Vec<String> = vec![];
+  let mut current = String::new();
+  let mut previous: Option<char> = None;
+  for c in member.chars() {
+    if !c.is_alphanumeric() {
+      if !current.is_empty() {
+        words.push(std::mem::take(&mut current));
+      }
+      previous = None;
+      continue;
+    }
+    let is_boundary =
+      c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_numeric());
+    if is_boundary && !current.is_empty() {
+      words.push(std::mem::take(&mut current));
+    }
+    current.push(c);
+    previous = Some(c);
+  }
+  if !current.is_empty() {
+    words.push(current);
+  }
+  words
+}
+
+fn capitalize(word: &str) -> String {
+  let mut chars = word.chars();
+  match chars.next() {
+    Some(first) => first
+      .to_uppercase()
+      .chain(chars.flat_map(|c| c.to_lowercase()))
+      .collect(),
+    None => String::new(),
+  }
+}
+
+/// Apply helper of template expression to member, e.g. `kebabCase`, `None` if helper is unknown
+fn apply_helper(helper: &str, member: &str) -> Option<String> {
+  let words = split_words(member);
+  let lower: Vec<String> = words.iter().map(|f| f.to_lowercase()).collect();
+  let output = match helper {
+    "camelCase" => lower
+      .iter()
+      .enumerate()
+      .map(|(i, f)| if i == 0 { f.clone() } else { capitalize(f) })
+      .collect(),
+    "kebabCase" => lower.join("-"),
+    "snakeCase" => lower.join("_"),
+    "upperCase" => member.to_uppercase(),
+    "lowerCase" => member.to_lowercase(),
+    _ => return None,
+  };
+  Some(output)
+}
+
+/// Render `{{member}}` and `{{helper member}}` expressions of template
+fn render(template: &str, member: &str) -> Result<String, String> {
+  let mut output = String::new();
+  let mut rest = template;
+  while let Some(start) = rest.find("{{") {
+    let Some(end) = rest[start..].find("}}") else {
+      return Err(format!("Invalid modularizeImports template {:?}", template));
+    };
+    output.push_str(&rest[..start]);
+    let expr: Vec<&str> = rest[start + 2..start + end].split_whitespace().collect();
+    match expr.as_slice() {
+      ["member"] => output.push_str(member),
+      [helper, "member"] => match apply_helper(helper, member) {
+        Some(rendered) => output.push_str(&rendered),
+        None => {
+          return Err(format!(
+            "Unknown modularizeImports helper {:?} in template {:?}",
+            helper, template
+          ))
+        }
+      },
+      _ => return Err(format!("Invalid modularizeImports template {:?}", template)),
+    }
+    rest = &rest[start + end + 2..];
+  }
+  output.push_str(rest);
+  Ok(output)
+}
+
+/// Check expressions and helpers of template, so that it can be rendered for any member
+pub fn validate_template(template: &str) -> Result<(), String> {
+  render(template, "member").map(|_| ())
+}
+
+impl ModularizeImports {
+  /// Rewritten import of named specifier, default and namespace specifiers are kept.
+  /// `None` if template can't be rendered, templates are validated when options are read
+  fn rewrite(
+    &self,
+    config: &ModularizeImport,
+    specifier: &ImportNamedSpecifier,
+  ) -> Option<ModuleItem> {
+    let member = match &specifier.imported {
+      Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+      Some(ModuleExportName::Str(s)) => s.value.to_string(),
+      None => specifier.local.sym.to_string(),
+    };
+    let template = config.members.get(&member).unwrap_or(&config.transform);
+    let src = match render(template, &member) {
+      Ok(src) => src,
+      Err(err) => {
+        debug!(target: "tswc", "skip modularize import {:?}: {}", member, err);
+        return None;
+      }
+    };
+    debug!(target: "tswc", "modularize import {:?} to {:?}", member, src);
+    let specifier = if config.skip_default_conversion {
+      ImportSpecifier::Named(specifier.clone())
+    } else {
+      ImportSpecifier::Default(ImportDefaultSpecifier {
+        span: DUMMY_SP,
+        local: specifier.local.clone(),
+      })
+    };
+    Some(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+      span: DUMMY_SP,
+      src: Box::new(Str {
+        span: DUMMY_SP,
+        value: src.into(),
+        raw: None,
+      }),
+      type_only: false,
+      with: None,
+      specifiers: vec![specifier],
+      phase: Default::default(),
+    })))
+  }
+}
+
+impl Fold for ModularizeImports {
+  noop_fold_type!();
+
+  /// Rewrite named imports of configured packages by template
+  /// e.g. import { map, debounce as d } from 'lodash' -> import map from 'lodash/map'; import d from 'lodash/debounce'
+  fn fold_module(&mut self, mut module: Module) -> Module {
+    let mut new_items: Vec<ModuleItem> = vec![];
+    for item in module.body {
+      let ModuleItem::ModuleDecl(ModuleDecl::Import(mut decl)) = item else {
+        new_items.push(item);
+        continue;
+      };
+      // import 'pkg' has side effects only
+      let is_modularized =
+        !decl.type_only && decl.phase == ImportPhase::Evaluation && !decl.specifiers.is_empty();
+      let config = self.packages.get(decl.src.value.as_str());
+      let Some(config) = config.filter(|_| is_modularized) else {
+        new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(decl)));
+        continue;
+      };
+      let mut kept = vec![];
+      let mut rewritten = vec![];
+      for specifier in decl.specifiers {
+        let item = match &specifier {
+          ImportSpecifier::Named(named) if !named.is_type_only => self.rewrite(config, named),
+          _ => None,
+        };
+        match item {
+          Some(item) => rewritten.push(item),
+          None => kept.push(specifier),
+        }
+      }
+      if !kept.is_empty() {
+        decl.specifiers = kept;
+        new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(decl)));
+      }
+      new_items.extend(rewritten);
+    }
+    module.body = new_items;
+    module
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn render_member() {
+    assert_eq!(
+      render("lodash/{{member}}", "debounce").unwrap(),
+      "lodash/debounce"
+    );
+    assert_eq!(render("{{ member }}/{{member}}", "a").unwrap(), "a/a");
+    assert_eq!(render("lodash", "map").unwrap(), "lodash");
+  }
+
+  #[test]
+  fn render_helpers() {
+    let render_helper = |helper: &str, member: &str| {
+      render(&format!("icons/{{{{{} member}}}}", helper), member).unwrap()
+    };
+    assert_eq!(
+      render_helper("kebabCase", "ArrowLeft2"),
+      "icons/arrow-left2"
+    );
+    assert_eq!(render_helper("snakeCase", "arrowLeft"), "icons/arrow_left");
+    assert_eq!(render_helper("camelCase", "arrow_left"), "icons/arrowLeft");
+    assert_eq!(render_helper("camelCase", "ArrowLeft"), "icons/arrowLeft");
+    assert_eq!(render_helper("upperCase", "map"), "icons/MAP");
+    assert_eq!(render_helper("lowerCase", "MapKeys"), "icons/mapkeys");
+  }
+
+  #[test]
+  fn reject_invalid_templates() {
+    assert!(validate_template("icons/{{titleCase member}}")
+      .unwrap_err()
+      .contains("titleCase"));
+    assert!(validate_template("icons/{{name}}").is_err());
+    assert!(validate_template("icons/{{kebabCase member extra}}").is_err());
+    assert!(validate_template("icons/{{kebabCase member}}").is_ok());
+  }
+
+  #[test]
+  fn reject_unclosed_expression() {
+    assert_eq!(
+      validate_template("lodash/{{member").unwrap_err(),
+      "Invalid modularizeImports template \"lodash/{{member\""
+    );
+    assert!(render("lodash/{{member}}/{{", "map").is_err());
+  }
+}
@@ -1,8 +1,16 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
 use log::debug;
 use serde::Deserialize;
-use swc_core::common::DUMMY_SP;
+use swc_core::common::{Mark, SyntaxContext, DUMMY_SP};
 use swc_core::ecma::ast::*;
-use swc_core::ecma::visit::{noop_fold_type, Fold};
+use swc_core::ecma::atoms::Atom;
+use swc_core::ecma::visit::{
+  noop_fold_type, noop_visit_mut_type, noop_visit_type, Fold, Visit, VisitMut, VisitMutWith,
+  VisitWith,
+};
+
+use crate::utils::to_module_export_name;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct Config {
@@ -22,98 +30,283 @@ impl NamedImportTransform {
   }
 }
 
+/// Members of namespace imports accessed statically, e.g. `Icons.Add` and `<Icons.Add />`
+#[derive(Debug, Default)]
+struct NamespaceUsage {
+  namespaces: HashSet<Id>,
+  members: HashMap<Id, BTreeSet<String>>,
+  /// Namespaces used other than static member access, e.g. `console.log(Icons)`
+  escaped: HashSet<Id>,
+}
+
+/// `Icons.Add` or `Icons['Add']`
+fn static_member(member: &MemberExpr) -> Option<(Id, String)> {
+  let Expr::Ident(obj) = &*member.obj else {
+    return None;
+  };
+  let prop = match &member.prop {
+    MemberProp::Ident(prop) => prop.sym.to_string(),
+    MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+      Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+      _ => return None,
+    },
+    MemberProp::PrivateName(_) => return None,
+  };
+  Some((obj.to_id(), prop))
+}
+
+impl Visit for NamespaceUsage {
+  noop_visit_type!();
+
+  fn visit_member_expr(&mut self, member: &MemberExpr) {
+    match static_member(member) {
+      Some((id, prop)) if self.namespaces.contains(&id) => {
+        self.members.entry(id).or_default().insert(prop);
+      }
+      _ => member.visit_children_with(self),
+    }
+  }
+
+  fn visit_jsx_member_expr(&mut self, member: &JSXMemberExpr) {
+    match &member.obj {
+      JSXObject::Ident(obj) if self.namespaces.contains(&obj.to_id()) => {
+        let prop = member.prop.sym.to_string();
+        self.members.entry(obj.to_id()).or_default().insert(prop);
+      }
+      _ => member.visit_children_with(self),
+    }
+  }
+
+  // Namespace object is immutable, assignment is left to fail at runtime
+  fn visit_simple_assign_target(&mut self, target: &SimpleAssignTarget) {
+    match target {
+      SimpleAssignTarget::Member(member) => match &*member.obj {
+        Expr::Ident(obj) if self.namespaces.contains(&obj.to_id()) => {
+          self.escaped.insert(obj.to_id());
+        }
+        _ => member.visit_children_with(self),
+      },
+      _ => target.visit_children_with(self),
+    }
+  }
+
+  fn visit_ident(&mut self, ident: &Ident) {
+    if self.namespaces.contains(&ident.to_id()) {
+      self.escaped.insert(ident.to_id());
+    }
+  }
+}
+
+/// Replace static member access of namespace imports with bindings of named imports
+struct NamespaceRewriter<'a> {
+  locals: &'a HashMap<Id, Vec<(String, Ident)>>,
+}
+
+impl<'a> NamespaceRewriter<'a> {
+  fn local(&self, id: &Id, prop: &str) -> Option<Ident> {
+    self
+      .locals
+      .get(id)?
+      .iter()
+      .find(|(member, _)| member == prop)
+      .map(|(_, local)| local.clone())
+  }
+}
+
+impl<'a> VisitMut for NamespaceRewriter<'a> {
+  noop_visit_mut_type!();
+
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    let member = match expr {
+      Expr::Member(member) => Some(&*member),
+      // `Icons?.Add`, namespace object is never nullish
+      Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+        OptChainBase::Member(member) => Some(member),
+        OptChainBase::Call(_) => None,
+      },
+      _ => None,
+    };
+    let local = member
+      .and_then(static_member)
+      .and_then(|(id, prop)| self.local(&id, &prop));
+    if let Some(local) = local {
+      *expr = Expr::Ident(local);
+      return;
+    }
+    expr.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_jsx_element_name(&mut self, name: &mut JSXElementName) {
+    if let JSXElementName::JSXMemberExpr(member) = name {
+      if let JSXObject::Ident(obj) = &member.obj {
+        if let Some(local) = self.local(&obj.to_id(), &member.prop.sym) {
+          *name = JSXElementName::Ident(local);
+        }
+      }
+    }
+  }
+}
+
+/// Binding of namespace member, lowercase names are avoided as they are intrinsic elements in jsx
+fn member_local(namespace: &str, member: &str) -> Ident {
+  let member: String = member
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '$' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect();
+  let mut name = format!("{}_{}", namespace, member);
+  if name.starts_with(|c: char| c.is_ascii_lowercase()) {
+    name = format!("_{}", name);
+  }
+  // Renamed by hygiene if it conflicts with other bindings
+  let ctxt = SyntaxContext::empty().apply_mark(Mark::new());
+  Ident::new(name.into(), DUMMY_SP, ctxt)
+}
+
+fn new_import(src: &Atom, specifier: ImportSpecifier) -> ModuleItem {
+  ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+    span: DUMMY_SP,
+    src: Box::new(Str {
+      span: DUMMY_SP,
+      value: src.clone(),
+      raw: None,
+    }),
+    type_only: false,
+    with: None,
+    specifiers: vec![specifier],
+    phase: Default::default(),
+  }))
+}
+
+impl NamedImportTransform {
+  fn is_optimized(&self, decl: &ImportDecl) -> bool {
+    // import 'pkg' has side effects only
+    if decl.type_only || decl.phase != ImportPhase::Evaluation || decl.specifiers.is_empty() {
+      return false;
+    }
+    self.packages.iter().any(|p| decl.src.value == *p)
+  }
+
+  fn is_optimized_export(&self, export: &NamedExport) -> bool {
+    let Some(src) = &export.src else {
+      return false;
+    };
+    !export.type_only && self.packages.iter().any(|p| src.value == *p)
+  }
+
+  /// Namespace import binding and its source, imports are already split
+  fn namespace_import(&self, item: &ModuleItem) -> Option<(Id, Atom)> {
+    let ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) = item else {
+      return None;
+    };
+    match decl.specifiers.as_slice() {
+      [ImportSpecifier::Namespace(s)] if self.is_optimized(decl) => {
+        Some((s.local.to_id(), decl.src.value.clone()))
+      }
+      _ => None,
+    }
+  }
+
+  /// Rewrite namespace imports into named imports if every use of the binding is static
+  /// member access, e.g. import * as Icons from 'pkg'; Icons.Add -> import { Add } from 'pkg'; Add
+  fn rewrite_namespaces(&self, module: &mut Module) {
+    let mut usage = NamespaceUsage::default();
+    for item in &module.body {
+      if let Some((id, _)) = self.namespace_import(item) {
+        usage.namespaces.insert(id);
+      }
+    }
+    if usage.namespaces.is_empty() {
+      return;
+    }
+    for item in &module.body {
+      if !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))) {
+        item.visit_with(&mut usage);
+      }
+    }
+    let locals: HashMap<Id, Vec<(String, Ident)>> = usage
+      .members
+      .into_iter()
+      .filter(|(id, _)| !usage.escaped.contains(id))
+      .map(|(id, members)| {
+        let locals = members
+          .into_iter()
+          .map(|member| {
+            let local = member_local(&id.0, &member);
+            (member, local)
+          })
+          .collect();
+        (id, locals)
+      })
+      .collect();
+    if locals.is_empty() {
+      return;
+    }
+    module.visit_mut_with(&mut NamespaceRewriter { locals: &locals });
+    let mut new_items: Vec<ModuleItem> = vec![];
+    for item in std::mem::take(&mut module.body) {
+      let Some((members, src)) = self
+        .namespace_import(&item)
+        .and_then(|(id, src)| Some((locals.get(&id)?, src)))
+      else {
+        new_items.push(item);
+        continue;
+      };
+      debug!(
+        target: "tswc",
+        "rewrite namespace import {:?} {:?}",
+        src, members
+      );
+      for (member, local) in members {
+        let specifier = ImportSpecifier::Named(ImportNamedSpecifier {
+          span: DUMMY_SP,
+          local: local.clone(),
+          imported: Some(to_module_export_name(member)),
+          is_type_only: false,
+        });
+        new_items.push(new_import(&src, specifier));
+      }
+    }
+    module.body = new_items;
+  }
+}
+
 impl Fold for NamedImportTransform {
   noop_fold_type!();
 
+  /// Split imports and re-exports of barrel packages into one declaration per specifier, so each
+  /// of them can be rewritten to the module declares it
+  /// e.g. import a, { b } from 'pkg' -> import a from 'pkg'; import { b } from 'pkg'
   fn fold_module(&mut self, mut module: Module) -> Module {
     let mut new_items: Vec<ModuleItem> = vec![];
     for item in module.body {
       match item {
-        ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => {
-          let src_value = decl.src.value.clone();
-          let mut skip_transform = false;
+        ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) if self.is_optimized(&decl) => {
           debug!(
             target: "tswc",
-            "self.pages {:?}",
-            self.packages
+            "split barrel import {:?}",
+            decl.src.value
           );
-          if self.packages.iter().any(|p| src_value == *p) {
-            debug!(
-              target: "tswc",
-              "self.named_imports src_value {:?}",
-              src_value
-            );
-            for specifier in &decl.specifiers {
-              match specifier {
-                ImportSpecifier::Named(specifier) => {
-                  // Add the import name as string to the set
-                  if let Some(imported) = &specifier.imported {
-                    match imported {
-                      ModuleExportName::Ident(_) => {
-                        let specifiers = ImportSpecifier::Named(specifier.clone());
-                        let import = ImportDecl {
-                          span: DUMMY_SP,
-                          src: Box::new(Str {
-                            span: DUMMY_SP,
-                            value: src_value.clone().into(),
-                            raw: None,
-                          }),
-                          type_only: false,
-                          with: None,
-                          specifiers: vec![specifiers],
-                          phase: Default::default(),
-                        };
-                        new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import)))
-                      }
-                      ModuleExportName::Str(_) => {
-                        let specifiers = ImportSpecifier::Named(specifier.clone());
-                        let import = ImportDecl {
-                          span: DUMMY_SP,
-                          src: Box::new(Str {
-                            span: DUMMY_SP,
-                            value: src_value.clone().into(),
-                            raw: None,
-                          }),
-                          type_only: false,
-                          with: None,
-                          specifiers: vec![specifiers],
-                          phase: Default::default(),
-                        };
-                        new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import)))
-                      }
-                    }
-                  } else {
-                    let specifiers = ImportSpecifier::Named(specifier.clone());
-                    let import = ImportDecl {
-                      span: DUMMY_SP,
-                      src: Box::new(Str {
-                        span: DUMMY_SP,
-                        value: src_value.clone().into(),
-                        raw: None,
-                      }),
-                      type_only: false,
-                      with: None,
-                      specifiers: vec![specifiers],
-                      phase: Default::default(),
-                    };
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import)))
-                  }
-                }
-                ImportSpecifier::Default(_) => {
-                  skip_transform = true;
-                  break;
-                }
-                ImportSpecifier::Namespace(_) => {
-                  skip_transform = true;
-                  break;
-                }
-              }
-            }
-          } else {
-            skip_transform = true
+          for specifier in &decl.specifiers {
+            new_items.push(new_import(&decl.src.value, specifier.clone()));
           }
-          if skip_transform {
-            new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(decl)));
+        }
+        // export { a, b as c } from 'pkg' -> export { a } from 'pkg'; export { b as c } from 'pkg'
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export))
+          if self.is_optimized_export(&export) =>
+        {
+          for specifier in &export.specifiers {
+            let export = NamedExport {
+              span: DUMMY_SP,
+              specifiers: vec![specifier.clone()],
+              ..export.clone()
+            };
+            new_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)));
           }
         }
         x => {
@@ -122,6 +315,84 @@ impl Fold for NamedImportTransform {
       }
     }
     module.body = new_items;
+    self.rewrite_namespaces(&mut module);
     module
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_core::common::{sync::Lrc, FileName, Globals, SourceMap, GLOBALS};
+  use swc_core::ecma::codegen::{text_writer::JsWriter, Emitter};
+  use swc_core::ecma::parser::{parse_file_as_module, EsSyntax, Syntax};
+
+  fn transform(source: &str) -> String {
+    GLOBALS.set(&Globals::new(), || {
+      let cm: Lrc<SourceMap> = Default::default();
+      let fm = cm.new_source_file(FileName::Anon.into(), source.to_string());
+      let syntax = Syntax::Es(EsSyntax {
+        jsx: true,
+        ..Default::default()
+      });
+      let module = parse_file_as_module(&fm, syntax, EsVersion::latest(), None, &mut vec![])
+        .expect("failed to parse");
+      let mut transform = NamedImportTransform::new(Config {
+        packages: vec!["pkg".into()],
+      });
+      let module = transform.fold_module(module);
+      let mut buf = vec![];
+      let mut emitter = Emitter {
+        cfg: Default::default(),
+        cm: cm.clone(),
+        comments: None,
+        wr: JsWriter::new(cm, "\n", &mut buf, None),
+      };
+      emitter.emit_module(&module).expect("failed to print");
+      String::from_utf8(buf).expect("invalid utf8")
+    })
+  }
+
+  #[test]
+  fn rewrite_static_members() {
+    let output = transform("import * as L from 'pkg'; L.map(L['filter'], <L.Item />);");
+    assert!(!output.contains("* as L"), "{}", output);
+    assert!(
+      output.contains("import { map as L_map } from \"pkg\""),
+      "{}",
+      output
+    );
+    assert!(
+      output.contains("import { filter as L_filter } from \"pkg\""),
+      "{}",
+      output
+    );
+    assert!(output.contains("L_map(L_filter, <L_Item/>)"), "{}", output);
+  }
+
+  #[test]
+  fn rewrite_optional_chain_members() {
+    let output =
+      transform("import * as L from 'pkg'; const a = L?.map; L?.filter(); L?.each?.(); L?.find.x;");
+    assert!(!output.contains("* as L"), "{}", output);
+    assert!(!output.contains("L?."), "{}", output);
+    assert!(output.contains("const a = L_map;"), "{}", output);
+    assert!(output.contains("L_filter()"), "{}", output);
+    assert!(output.contains("L_each?.()"), "{}", output);
+    assert!(output.contains("L_find.x"), "{}", output);
+  }
+
+  #[test]
+  fn keep_escaped_namespace() {
+    let output = transform("import * as L from 'pkg'; L.map(); console.log(L);");
+    assert!(output.contains("import * as L from \"pkg\""), "{}", output);
+    assert!(output.contains("L.map()"), "{}", output);
+  }
+
+  #[test]
+  fn keep_dynamic_members() {
+    let output = transform("import * as L from 'pkg'; L?.[name]; L.map();");
+    assert!(output.contains("import * as L from \"pkg\""), "{}", output);
+    assert!(output.contains("L?.[name]"), "{}", output);
+  }
+}
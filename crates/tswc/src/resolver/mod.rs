@@ -1,2 +1,4 @@
+mod package_json;
 mod resolve;
+pub use package_json::PackageJson;
 pub use resolve::{Format, Resolver, ResolverOptions};
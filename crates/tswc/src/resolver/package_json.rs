@@ -0,0 +1,283 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+use serde_json::Value;
+use sugar_path::SugarPath;
+
+use super::Format;
+use crate::utils::package_name_of_path;
+
+#[derive(Default, Debug, Clone)]
+pub struct PackageJson {
+  /// Directory contains the package.json
+  pub root: PathBuf,
+  pub raw: Value,
+}
+
+fn resolve_conditions<'a>(target: &'a Value, conditions: &[&str]) -> Option<&'a str> {
+  match target {
+    Value::String(s) => Some(s.as_str()),
+    Value::Array(targets) => targets
+      .iter()
+      .find_map(|f| resolve_conditions(f, conditions)),
+    Value::Object(map) => conditions
+      .iter()
+      .find_map(|c| map.get(*c))
+      .and_then(|f| resolve_conditions(f, conditions)),
+    _ => None,
+  }
+}
+
+fn collect_targets(target: &Value, targets: &mut Vec<String>) {
+  match target {
+    Value::String(s) => targets.push(s.clone()),
+    Value::Array(values) => values.iter().for_each(|f| collect_targets(f, targets)),
+    Value::Object(map) => map.values().for_each(|f| collect_targets(f, targets)),
+    _ => {}
+  }
+}
+
+impl PackageJson {
+  pub fn read(root: &Path) -> Option<PackageJson> {
+    let content = fs::read_to_string(root.join("package.json")).ok()?;
+    let raw = serde_json::from_str(&content).ok()?;
+    Some(PackageJson {
+      root: root.to_path_buf(),
+      raw,
+    })
+  }
+  /// package.json of the node_modules package contains file
+  pub fn read_installed(path: &str) -> Option<PackageJson> {
+    let name = package_name_of_path(path)?;
+    let (prefix, _) = path.rsplit_once("node_modules/")?;
+    PackageJson::read(&Path::new(prefix).join("node_modules").join(name))
+  }
+  pub fn name(&self) -> Option<&str> {
+    self.raw.get("name").and_then(|f| f.as_str())
+  }
+  pub fn version(&self) -> Option<&str> {
+    self.raw.get("version").and_then(|f| f.as_str())
+  }
+  /// Package names declared in dependency field, e.g. `dependencies` or `devDependencies`
+  pub fn dependencies(&self, field: &str) -> Vec<String> {
+    self
+      .raw
+      .get(field)
+      .and_then(|f| f.as_object())
+      .map(|f| f.keys().cloned().collect())
+      .unwrap_or_default()
+  }
+  /// `sideEffects: false`, no module of the package has side effects
+  pub fn is_side_effects_free(&self) -> bool {
+    self.raw.get("sideEffects") == Some(&Value::Bool(false))
+  }
+  /// Evaluating file may have side effects according to `sideEffects`, every file has side effects
+  /// if it is not declared. Globs without `/` match file name in any directory, like webpack
+  pub fn has_side_effects(&self, file: &Path) -> bool {
+    let patterns = match self.raw.get("sideEffects") {
+      Some(Value::Bool(value)) => return *value,
+      Some(Value::Array(patterns)) => patterns,
+      _ => return true,
+    };
+    let Some(relative) = file.strip_prefix(&self.root).ok().and_then(|f| f.to_str()) else {
+      return true;
+    };
+    let relative = relative.replace('\\', "/");
+    patterns.iter().filter_map(|f| f.as_str()).any(|pattern| {
+      let pattern = pattern.trim_start_matches("./");
+      let pattern = if pattern.contains('/') {
+        pattern.to_string()
+      } else {
+        format!("**/{}", pattern)
+      };
+      Pattern::new(&pattern).is_ok_and(|p| p.matches(&relative))
+    })
+  }
+  /// Entry file of `subpath` e.g. `.` or `./utils`, declared by `exports` field, fallback to `main`
+  pub fn entry(&self, subpath: &str, format: &Format) -> Option<PathBuf> {
+    let conditions = match format {
+      Format::CJS => ["require", "node", "default"],
+      Format::ESM => ["import", "node", "default"],
+    };
+    if let Some(exports) = self.raw.get("exports") {
+      let target = match exports {
+        Value::Object(map) if map.keys().any(|k| k.starts_with('.')) => map.get(subpath),
+        _ if subpath == "." => Some(exports),
+        _ => None,
+      };
+      return target
+        .and_then(|f| resolve_conditions(f, &conditions))
+        .map(|f| self.root.join(f).normalize());
+    }
+    if subpath != "." {
+      return Some(self.root.join(subpath).normalize());
+    }
+    let main = match format {
+      Format::ESM => self.raw.get("module").or_else(|| self.raw.get("main")),
+      Format::CJS => self.raw.get("main"),
+    };
+    let main = main.and_then(|f| f.as_str()).unwrap_or("index.js");
+    Some(self.root.join(main).normalize())
+  }
+  fn is_target(&self, target: &Value, file: &Path) -> bool {
+    let mut targets = vec![];
+    collect_targets(target, &mut targets);
+    targets
+      .iter()
+      .any(|f| self.root.join(f).normalize() == file)
+  }
+  /// Subpath of file e.g. `./Button`, `.` for the main entry. Only subpaths declared by `exports`
+  /// of any condition are public, every file is public if `exports` is not declared
+  pub fn subpath_of(&self, file: &Path) -> Option<String> {
+    let relative = file
+      .strip_prefix(&self.root)
+      .ok()?
+      .to_str()?
+      .replace('\\', "/");
+    let Some(exports) = self.raw.get("exports") else {
+      return Some(format!("./{}", relative));
+    };
+    let map = match exports {
+      Value::Object(map) if map.keys().any(|k| k.starts_with('.')) => map,
+      _ => return self.is_target(exports, file).then(|| ".".to_string()),
+    };
+    if let Some((key, _)) = map
+      .iter()
+      .find(|(key, target)| !key.contains('*') && self.is_target(target, file))
+    {
+      return Some(key.clone());
+    }
+    // "./*": "./dist/*.js"
+    let is_excluded = |subpath: &str| {
+      map.iter().any(|(key, target)| {
+        target.is_null()
+          && match key.split_once('*') {
+            Some((prefix, suffix)) => subpath.starts_with(prefix) && subpath.ends_with(suffix),
+            None => key == subpath,
+          }
+      })
+    };
+    for (key, target) in map {
+      let Some((prefix, suffix)) = key.split_once('*') else {
+        continue;
+      };
+      let mut targets = vec![];
+      collect_targets(target, &mut targets);
+      for target in targets {
+        let Some((target_prefix, target_suffix)) = target.split_once('*') else {
+          continue;
+        };
+        let target_prefix = target_prefix.trim_start_matches("./");
+        let star = relative
+          .strip_prefix(target_prefix)
+          .and_then(|f| f.strip_suffix(target_suffix));
+        if let Some(star) = star {
+          let subpath = format!("{}{}{}", prefix, star, suffix);
+          if !is_excluded(&subpath) {
+            return Some(subpath);
+          }
+        }
+      }
+    }
+    None
+  }
+  /// Every file declared by `main`, `module` and `exports` of all conditions, patterns are skipped
+  pub fn targets(&self) -> Vec<PathBuf> {
+    let mut targets = vec![];
+    for field in ["main", "module", "exports"] {
+      if let Some(value) = self.raw.get(field) {
+        collect_targets(value, &mut targets);
+      }
+    }
+    let mut targets: Vec<PathBuf> = targets
+      .into_iter()
+      .filter(|f| !f.contains('*'))
+      .map(|f| self.root.join(f).normalize())
+      .collect();
+    targets.sort();
+    targets.dedup();
+    targets
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  fn subpath_of(raw: Value, file: &str) -> Option<String> {
+    let pkg = PackageJson {
+      root: PathBuf::from("/pkg"),
+      raw,
+    };
+    pkg.subpath_of(&Path::new("/pkg").join(file))
+  }
+
+  #[test]
+  fn every_file_is_public_without_exports() {
+    let raw = json!({ "main": "./dist/index.js" });
+    assert_eq!(
+      subpath_of(raw, "dist/utils.js").as_deref(),
+      Some("./dist/utils.js")
+    );
+  }
+
+  #[test]
+  fn main_entry_of_exports() {
+    let raw = json!({ "exports": "./dist/index.js" });
+    assert_eq!(
+      subpath_of(raw.clone(), "dist/index.js").as_deref(),
+      Some(".")
+    );
+    assert_eq!(subpath_of(raw, "dist/utils.js"), None);
+
+    let raw = json!({ "exports": { "import": "./esm/index.js", "require": "./cjs/index.js" } });
+    assert_eq!(
+      subpath_of(raw.clone(), "esm/index.js").as_deref(),
+      Some(".")
+    );
+    assert_eq!(subpath_of(raw, "cjs/index.js").as_deref(), Some("."));
+  }
+
+  #[test]
+  fn conditional_exports() {
+    let raw = json!({
+      "exports": {
+        ".": { "import": "./dist/index.mjs", "require": "./dist/index.cjs" },
+        "./button": { "types": "./dist/button.d.ts", "default": ["./dist/button.js"] },
+      }
+    });
+    assert_eq!(
+      subpath_of(raw.clone(), "dist/index.cjs").as_deref(),
+      Some(".")
+    );
+    assert_eq!(
+      subpath_of(raw.clone(), "dist/button.js").as_deref(),
+      Some("./button")
+    );
+    assert_eq!(subpath_of(raw, "dist/internal.js"), None);
+  }
+
+  #[test]
+  fn pattern_exports() {
+    let raw = json!({
+      "exports": {
+        "./*": "./dist/*.js",
+        "./internal/*": null,
+        "./icons/*": { "import": "./esm/icons/*.mjs" },
+      }
+    });
+    assert_eq!(
+      subpath_of(raw.clone(), "dist/Button.js").as_deref(),
+      Some("./Button")
+    );
+    assert_eq!(subpath_of(raw.clone(), "dist/internal/state.js"), None);
+    assert_eq!(
+      subpath_of(raw.clone(), "esm/icons/Add.mjs").as_deref(),
+      Some("./icons/Add")
+    );
+    assert_eq!(subpath_of(raw, "src/Button.ts"), None);
+  }
+}
@@ -40,7 +40,8 @@ impl Default for ResolverOptions {
   }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Format {
   CJS,
   ESM,
@@ -52,15 +53,28 @@ impl Resolver {
       externals: options.externals,
       tsconfig: options.tsconfig.clone(),
       modules: options.modules.clone(),
-      ..ResolverOptions::default()
     };
     let extensions = vec![
       ".ts".into(),
       ".tsx".into(),
+      ".mts".into(),
+      ".cts".into(),
       ".js".into(),
       ".jsx".into(),
+      ".mjs".into(),
+      ".cjs".into(),
       ".json".into(),
     ];
+    // Same as tsc, `import './a.js'` may point to `./a.ts` source file
+    let extension_alias: Vec<(String, Vec<String>)> = vec![
+      (
+        ".js".into(),
+        vec![".ts".into(), ".tsx".into(), ".js".into()],
+      ),
+      (".jsx".into(), vec![".tsx".into(), ".jsx".into()]),
+      (".mjs".into(), vec![".mts".into(), ".mjs".into()]),
+      (".cjs".into(), vec![".cts".into(), ".cjs".into()]),
+    ];
     let cjs_resolved_options = ResolveOptions {
       tsconfig: Some(TsconfigOptions {
         config_file: options.tsconfig.clone(),
@@ -68,6 +82,7 @@ impl Resolver {
       }),
       // TODO: exts should config
       extensions: extensions.clone(),
+      extension_alias: extension_alias.clone(),
       exports_fields: vec![vec!["exports".into()]],
       // TODO: create esm resolver
       // TODO: create browser resolver
@@ -86,6 +101,7 @@ impl Resolver {
       }),
       // TODO: exts should config
       extensions,
+      extension_alias,
       exports_fields: vec![vec!["exports".into()]],
       // TODO: create esm resolver
       // TODO: create browser resolver
@@ -110,8 +126,7 @@ impl Resolver {
     }
   }
   pub fn resolve_context(&self, context: &str) -> Option<String> {
-    let path_str = find_up_dir(PathBuf::from(context));
-    return path_str;
+    find_up_dir(PathBuf::from(context))
   }
   /// Format Default is CJS
   pub fn resolve(
@@ -127,11 +142,7 @@ impl Resolver {
       Format::ESM => &self.mjs_resolver,
     };
 
-    if path_str.is_none() {
-      return None;
-    }
-
-    let path = path_str.clone().unwrap();
+    let path = path_str.clone()?;
     let path = path.as_path();
 
     if self.options.externals.contains(&String::from(specifier)) {
@@ -153,7 +164,7 @@ impl Resolver {
 
     let mut built_in = false;
 
-    let resolved_path: Option<String> = match resolver.resolve(path, &specifier) {
+    let resolved_path: Option<String> = match resolver.resolve(path, specifier) {
       Err(error) => {
         match error {
           ResolveError::Builtin(spec) => {
@@ -191,3 +202,71 @@ impl Resolver {
     })
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use super::*;
+
+  /// Resolve specifier from `src/index.ts` of fixture, returns path relative to `src`
+  fn resolve(specifier: &str, format: Format) -> Option<String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/extension_alias");
+    let resolver = Resolver::new(ResolverOptions {
+      tsconfig: root.join("tsconfig.json"),
+      ..Default::default()
+    });
+    let src = root.join("src");
+    let context = src.join("index.ts");
+    let resolved = resolver.resolve(specifier, context.to_str().unwrap(), Some(format))?;
+    let abs_path = PathBuf::from(resolved.abs_path?);
+    Some(abs_path.strip_prefix(&src).ok()?.to_str()?.to_string())
+  }
+
+  #[test]
+  fn js_to_typescript_sources() {
+    for format in [Format::CJS, Format::ESM] {
+      assert_eq!(resolve("./a.js", format.clone()).as_deref(), Some("a.ts"));
+      assert_eq!(
+        resolve("./view.js", format.clone()).as_deref(),
+        Some("view.tsx")
+      );
+      assert_eq!(
+        resolve("./view.jsx", format.clone()).as_deref(),
+        Some("view.tsx")
+      );
+      // Falls back to JavaScript file if there is no TypeScript source
+      assert_eq!(resolve("./plain.js", format).as_deref(), Some("plain.js"));
+    }
+  }
+
+  #[test]
+  fn mjs_to_mts() {
+    assert_eq!(
+      resolve("./esm.mjs", Format::ESM).as_deref(),
+      Some("esm.mts")
+    );
+    assert_eq!(
+      resolve("./esm.mjs", Format::CJS).as_deref(),
+      Some("esm.mts")
+    );
+  }
+
+  #[test]
+  fn cjs_to_cts() {
+    assert_eq!(
+      resolve("./legacy.cjs", Format::CJS).as_deref(),
+      Some("legacy.cts")
+    );
+    assert_eq!(
+      resolve("./legacy.cjs", Format::ESM).as_deref(),
+      Some("legacy.cts")
+    );
+  }
+
+  #[test]
+  fn missing_source() {
+    let resolved = resolve("./missing.js", Format::CJS);
+    assert_eq!(resolved, None);
+  }
+}
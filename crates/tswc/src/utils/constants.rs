@@ -3,4 +3,53 @@ use regex::Regex;
 
 pub static QUERY_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\?.*").expect("query re init failed"));
 pub static SCRIPT_RE: Lazy<Regex> =
-  Lazy::new(|| Regex::new(r"\.ts$|\.tsx$|\.js$|\.jsx$").expect("ext re init failed"));
+  Lazy::new(|| Regex::new(r"\.(?:[mc]?ts|tsx|[mc]?js|jsx)$").expect("ext re init failed"));
+pub static DTS_RE: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"\.d\.[mc]?ts$").expect("dts re init failed"));
+/// Globs of test files relative to root
+pub const TEST_GLOBS: [&str; 4] = [
+  "**/*.test.*",
+  "**/*.spec.*",
+  "**/__tests__/**",
+  "**/__mocks__/**",
+];
+/// Packages optimized as barrel packages if installed, like `optimizePackageImports` of Next.js
+pub const BARREL_PRESETS: [&str; 20] = [
+  "@ant-design/icons",
+  "@headlessui/react",
+  "@material-ui/core",
+  "@material-ui/icons",
+  "@mui/icons-material",
+  "@mui/material",
+  "@tabler/icons-react",
+  "@tremor/react",
+  "@visx/visx",
+  "ahooks",
+  "antd",
+  "date-fns",
+  "effect",
+  "lodash-es",
+  "lucide-react",
+  "ramda",
+  "react-bootstrap",
+  "react-use",
+  "recharts",
+  "rxjs",
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn script_extensions() {
+    for file in [
+      "a.ts", "a.tsx", "a.mts", "a.cts", "a.js", "a.jsx", "a.mjs", "a.cjs",
+    ] {
+      assert!(SCRIPT_RE.is_match(file), "{}", file);
+    }
+    for file in ["a.mtsx", "a.ctsx", "a.mjsx", "a.cjsx", "a.json", "a.css"] {
+      assert!(!SCRIPT_RE.is_match(file), "{}", file);
+    }
+  }
+}
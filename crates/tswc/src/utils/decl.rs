@@ -1,3 +1,7 @@
+use serde::{Deserialize, Serialize};
+use swc_core::common::{BytePos, Span, DUMMY_SP};
+use swc_core::ecma::ast::{Ident, ModuleExportName, Str};
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ImportType {
   #[doc = "
@@ -35,10 +39,48 @@ pub struct ImportSpecifier {
   pub src: Option<String>,
   #[doc = " Type of import statement "]
   pub t: ImportType,
+  #[doc = " Span of source, quotes included "]
+  pub src_span: Option<Span>,
+  #[doc = " Span of import statement or dynamic import expression "]
+  pub statement_span: Span,
+  #[doc = " Position of dynamic import bracket "]
+  pub dynamic: Option<BytePos>,
+  #[doc = " Span of import attributes "]
+  pub attributes: Option<Span>,
+}
+
+/// Kind of exported declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportKind {
+  Function,
+  Class,
+  Const,
+  Enum,
+  Namespace,
+  /// Interfaces, type aliases and type only exports
+  Type,
+  /// Re-exports or expressions, e.g. `export default {}`
+  #[default]
+  Value,
+}
+
+impl ExportKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ExportKind::Function => "function",
+      ExportKind::Class => "class",
+      ExportKind::Const => "const",
+      ExportKind::Enum => "enum",
+      ExportKind::Namespace => "namespace",
+      ExportKind::Type => "type",
+      ExportKind::Value => "value",
+    }
+  }
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ExportSpecifier {
   #[doc = " Export name "]
   pub n: String,
@@ -46,4 +88,22 @@ pub struct ExportSpecifier {
   pub ln: Option<String>,
   #[doc = " Source name "]
   pub src: Option<String>,
+  #[doc = " Span of export name "]
+  pub span: Option<Span>,
+  #[doc = " Span of local name "]
+  pub local_span: Option<Span>,
+  #[doc = " Kind of declaration "]
+  pub kind: ExportKind,
+}
+
+/// `a` or `'a-b'` in `export { a, 'a-b' } from 'b'`, keywords are valid names e.g. `default`
+pub fn to_module_export_name(name: &str) -> ModuleExportName {
+  let mut chars = name.chars();
+  let is_ident =
+    chars.next().is_some_and(Ident::is_valid_start) && chars.all(Ident::is_valid_continue);
+  if is_ident {
+    ModuleExportName::Ident(Ident::new(name.into(), DUMMY_SP, Default::default()))
+  } else {
+    ModuleExportName::Str(Str::from(name))
+  }
 }
@@ -1,4 +1,6 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use sugar_path::SugarPath;
 
 pub fn find_up_dir(context: PathBuf) -> Option<String> {
   if context.is_dir() {
@@ -9,3 +11,42 @@ pub fn find_up_dir(context: PathBuf) -> Option<String> {
       .and_then(|parent| find_up_dir(parent.to_path_buf()))
   }
 }
+
+/// Emitted extension of a script file, `.mts` and `.cts` keep their module kind like tsc does
+pub fn output_ext(path: &str) -> &'static str {
+  match path.as_path().extension().and_then(|f| f.to_str()) {
+    Some("mts") | Some("mjs") => "mjs",
+    Some("cts") | Some("cjs") => "cjs",
+    _ => "js",
+  }
+}
+
+/// Closest directory contains `package.json` of `path`
+pub fn find_up_package_root(path: &Path) -> Option<PathBuf> {
+  path
+    .ancestors()
+    .find(|dir| dir.join("package.json").is_file())
+    .map(|dir| dir.to_path_buf())
+}
+
+/// Package name of bare specifier, e.g. `@mui/material/Button` -> `@mui/material`
+pub fn package_name_of_specifier(specifier: &str) -> Option<String> {
+  if specifier.starts_with('.') || specifier.starts_with('/') || specifier.starts_with('#') {
+    return None;
+  }
+  let mut segments = specifier.split('/');
+  let first = segments.next().filter(|f| !f.is_empty())?;
+  if first.starts_with('@') {
+    segments
+      .next()
+      .map(|second| format!("{}/{}", first, second))
+  } else {
+    Some(first.to_string())
+  }
+}
+
+/// Package name of filepath inside node_modules, e.g. `/node_modules/@mui/material/Button/index.js` -> `@mui/material`
+pub fn package_name_of_path(path: &str) -> Option<String> {
+  let (_, subpath) = path.rsplit_once("node_modules/")?;
+  package_name_of_specifier(subpath)
+}
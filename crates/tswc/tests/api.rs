@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+use tswc::apis::{check, transform, TransformApiOptions, TransformOptions, TransformResult};
+
+fn fixture() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/api")
+}
+
+fn api_options(baseline: &Path, fail_on_breaking: Option<bool>) -> TransformApiOptions {
+  TransformApiOptions {
+    baseline: Some(baseline.to_str().unwrap().to_string()),
+    update: None,
+    fail_on_breaking,
+  }
+}
+
+/// Check fixture against baseline written into a temporary dir
+fn check_api(baseline: &str, fail_on_breaking: Option<bool>) -> napi::Result<TransformResult> {
+  let dir = TempDir::new().unwrap();
+  let path = dir.path().join("tswc-api.json");
+  fs::write(&path, baseline).unwrap();
+  check(TransformOptions {
+    root: fixture().to_str().unwrap().to_string(),
+    entries: Some(vec!["src/index.ts".into()]),
+    api: Some(api_options(&path, fail_on_breaking)),
+    ..Default::default()
+  })
+}
+
+const BROKEN_BASELINE: &str = r#"{
+  "entries": {
+    "src/index.ts": [
+      { "name": "render", "kind": "function" },
+      { "name": "Theme", "kind": "type" },
+      { "name": "version", "kind": "const" }
+    ]
+  }
+}"#;
+
+#[test]
+fn report_breaking_changes() {
+  let result = check_api(BROKEN_BASELINE, Some(false)).expect("diagnostics are reported");
+  let codes: Vec<&str> = result
+    .diagnostics
+    .iter()
+    .filter(|f| f.code.starts_with("api-"))
+    .map(|f| f.code.as_str())
+    .collect();
+  assert_eq!(codes, ["api-removed"]);
+}
+
+#[test]
+fn fail_on_breaking_changes_by_default() {
+  let err = check_api(BROKEN_BASELINE, None)
+    .err()
+    .expect("breaking changes should be rejected");
+  assert!(err.reason.contains("Theme"), "{}", err.reason);
+}
+
+#[test]
+fn report_invalid_baseline() {
+  let result = check_api("{ \"entries\": [", Some(false)).expect("diagnostics are reported");
+  assert!(result
+    .diagnostics
+    .iter()
+    .any(|f| f.code == "api-baseline-invalid" && f.severity == "error"));
+}
+
+#[test]
+fn report_missing_baseline() {
+  let dir = TempDir::new().unwrap();
+  let result = check(TransformOptions {
+    root: fixture().to_str().unwrap().to_string(),
+    entries: Some(vec!["src/index.ts".into()]),
+    api: Some(api_options(&dir.path().join("tswc-api.json"), None)),
+    ..Default::default()
+  })
+  .expect("missing baseline is not a breaking change");
+  assert!(result
+    .diagnostics
+    .iter()
+    .any(|f| f.code == "api-baseline-missing" && f.severity == "warning"));
+}
+
+#[test]
+fn report_created_baseline() {
+  let dir = TempDir::new().unwrap();
+  let baseline = dir.path().join("api/baseline.json");
+  let result = transform(TransformOptions {
+    root: fixture().to_str().unwrap().to_string(),
+    output: Some(dir.path().join("dist").to_str().unwrap().to_string()),
+    entries: Some(vec!["src/index.ts".into()]),
+    api: Some(api_options(&baseline, None)),
+    ..Default::default()
+  })
+  .expect("fixture should compile");
+  let created: Vec<_> = result
+    .diagnostics
+    .iter()
+    .filter(|f| f.code == "api-baseline-created")
+    .collect();
+  assert_eq!(created.len(), 1);
+  assert_eq!(created[0].file.as_deref(), baseline.to_str());
+  let content = fs::read_to_string(&baseline).expect("baseline should be written");
+  assert!(content.contains("\"render\""));
+}
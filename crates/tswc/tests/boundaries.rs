@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use tswc::apis::{check, TransformBoundaryRule, TransformOptions, TransformResult};
+
+fn check_rule(severity: Option<&str>) -> napi::Result<TransformResult> {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/boundaries");
+  check(TransformOptions {
+    root: root.to_str().unwrap().to_string(),
+    entries: Some(vec!["src/index.ts".into()]),
+    rules: Some(vec![TransformBoundaryRule {
+      from: Some(vec!["src/ui/**".into()]),
+      to: vec!["src/server/**".into()],
+      except: None,
+      message: None,
+      severity: severity.map(|f| f.to_string()),
+    }]),
+    ..Default::default()
+  })
+}
+
+#[test]
+fn report_each_import_once() {
+  let result = check_rule(None).expect("fixture should check");
+  let mut lines: Vec<u32> = result
+    .diagnostics
+    .iter()
+    .filter(|f| f.code == "boundary-violation")
+    .filter_map(|f| f.line)
+    .collect();
+  lines.sort();
+  // Repeated imports of the same specifier are merged, the re-export is reported on its own
+  assert_eq!(lines, [1, 4]);
+}
+
+#[test]
+fn unknown_rule_severity() {
+  let err = check_rule(Some("fatal"))
+    .err()
+    .expect("unknown severity should be rejected");
+  assert!(err.reason.contains("fatal"));
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use tswc::apis::{check, TransformCircularOptions, TransformDiagnostic, TransformOptions};
+
+fn check_circular(
+  allowlist: Option<Vec<String>>,
+  severity: Option<&str>,
+) -> Vec<TransformDiagnostic> {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/circular");
+  let result = check(TransformOptions {
+    root: root.to_str().unwrap().to_string(),
+    entries: Some(vec!["src/index.ts".into()]),
+    circular: Some(TransformCircularOptions {
+      allowlist,
+      severity: severity.map(|f| f.to_string()),
+    }),
+    ..Default::default()
+  })
+  .expect("fixture should check");
+  result
+    .diagnostics
+    .into_iter()
+    .filter(|f| f.code == "circular-dependency")
+    .collect()
+}
+
+fn messages(diagnostics: &[TransformDiagnostic]) -> Vec<&str> {
+  let mut messages: Vec<&str> = diagnostics.iter().map(|f| f.message.as_str()).collect();
+  messages.sort();
+  messages
+}
+
+#[test]
+fn report_runtime_cycles() {
+  let diagnostics = check_circular(None, None);
+  assert_eq!(
+    messages(&diagnostics),
+    [
+      "Circular dependency: src/a.ts:1 -> src/b.ts:1 -> src/c.ts:1 -> src/a.ts",
+      "Circular dependency: src/self.ts:1 -> src/self.ts",
+      "Circular dependency: src/vendor/first.ts:1 -> src/vendor/second.ts:1 -> src/vendor/first.ts",
+    ]
+  );
+  assert!(diagnostics.iter().all(|f| f.severity == "warning"));
+}
+
+#[test]
+fn skip_allowed_cycles() {
+  let diagnostics = check_circular(Some(vec!["src/vendor/**".into()]), Some("error"));
+  assert_eq!(diagnostics.len(), 2);
+  assert!(diagnostics.iter().all(|f| !f.message.contains("vendor")));
+  assert!(diagnostics.iter().all(|f| f.severity == "error"));
+}
+
+#[test]
+fn unknown_severity() {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/circular");
+  let err = check(TransformOptions {
+    root: root.to_str().unwrap().to_string(),
+    circular: Some(TransformCircularOptions {
+      allowlist: None,
+      severity: Some("fatal".into()),
+    }),
+    ..Default::default()
+  })
+  .err()
+  .expect("unknown severity should be rejected");
+  assert!(err.reason.contains("fatal"));
+}
@@ -0,0 +1,16 @@
+use std::path::Path;
+
+use tswc::apis::{check, TransformOptions};
+
+#[test]
+fn missing_entry() {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/entries");
+  let err = check(TransformOptions {
+    root: root.to_str().unwrap().to_string(),
+    entries: Some(vec!["src/index.ts".into(), "src/main.ts".into()]),
+    ..Default::default()
+  })
+  .err()
+  .expect("missing entry should be rejected");
+  assert!(err.reason.contains("src/main.ts"), "{}", err.reason);
+}
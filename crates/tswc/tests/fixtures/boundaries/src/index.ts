@@ -0,0 +1,3 @@
+import { Page } from './ui/page';
+
+console.log(Page);
@@ -0,0 +1,3 @@
+export const connect = () => 'db';
+export const query = (db: string) => db;
+export const close = () => {};
@@ -0,0 +1,5 @@
+import { query } from '../server/db';
+import { connect } from '../server/db';
+
+export { close } from '../server/db';
+export const Page = () => query(connect());
@@ -0,0 +1,7 @@
+import { a } from './a';
+import { self } from './self';
+import { left } from './types/left';
+import { load } from './lazy/load';
+import { vendor } from './vendor/first';
+
+console.log(a, self, left, load, vendor);
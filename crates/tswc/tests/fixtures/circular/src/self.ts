@@ -0,0 +1,3 @@
+import * as own from './self';
+
+export const self = () => own;
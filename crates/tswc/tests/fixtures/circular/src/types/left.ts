@@ -0,0 +1,4 @@
+import type { Right } from './right';
+
+export type Left = { right?: Right };
+export const left = 'left';
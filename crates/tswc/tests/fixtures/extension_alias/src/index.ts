@@ -0,0 +1,5 @@
+export { a } from './a.js';
+export { View } from './view.js';
+export { plain } from './plain.js';
+export { esm } from './esm.mjs';
+export { legacy } from './legacy.cjs';
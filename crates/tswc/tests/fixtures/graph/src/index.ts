@@ -0,0 +1,6 @@
+import { a } from './a';
+import { main } from 'pkg';
+import { util } from 'pkg/lib/util';
+
+export const all = [a, main, util];
+export const load = () => import('./lazy.js');
@@ -0,0 +1,7 @@
+import { Button } from "./components/Button.js";
+import Card from "./components/Card.js";
+import { ready } from "./setup/index.js";
+const theme = {
+    palette: {}
+};
+console.log(Button, Card, ready, theme);
@@ -0,0 +1,2 @@
+export * from './Button'
+export { default as Card } from './Card'
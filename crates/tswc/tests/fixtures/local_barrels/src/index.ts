@@ -0,0 +1,8 @@
+import { Button, Card } from './components'
+import { ready } from './setup'
+import type { Theme } from './theme'
+import { Palette } from './theme'
+
+const theme: Theme = { palette: {} as Palette }
+
+console.log(Button, Card, ready, theme)
@@ -0,0 +1,2 @@
+import './register'
+export { ready } from './state'
@@ -0,0 +1,3 @@
+import { mark } from './state'
+
+mark()
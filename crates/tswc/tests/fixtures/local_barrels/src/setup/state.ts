@@ -0,0 +1,5 @@
+export const ready = { value: false }
+
+export function mark() {
+  ready.value = true
+}
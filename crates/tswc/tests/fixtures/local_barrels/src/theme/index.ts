@@ -0,0 +1 @@
+export type { Theme, Palette } from './types'
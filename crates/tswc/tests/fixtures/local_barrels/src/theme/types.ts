@@ -0,0 +1,5 @@
+export interface Palette {}
+
+export interface Theme {
+  palette: Palette
+}
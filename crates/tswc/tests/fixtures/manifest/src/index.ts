@@ -0,0 +1,7 @@
+import { readFileSync } from 'fs';
+import { add, sub } from './math';
+
+export { version } from './version';
+export const load = () => import('./lazy.js');
+export const total = add(1, sub(3, 2));
+export const read = readFileSync;
@@ -0,0 +1,5 @@
+import { esm } from './esm.mjs';
+import { cjs } from './legacy.cjs';
+
+export const both = [esm, cjs];
+export const lazy = () => import('./lazy.mjs');
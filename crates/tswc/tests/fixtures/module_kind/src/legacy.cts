@@ -0,0 +1 @@
+export const cjs = 'cjs';
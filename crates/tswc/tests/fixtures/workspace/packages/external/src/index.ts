@@ -0,0 +1 @@
+export const external = 'external';
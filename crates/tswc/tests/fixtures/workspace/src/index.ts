@@ -0,0 +1,5 @@
+import { inline } from '@ws/inline';
+import { external } from '@ws/external';
+import { output } from '@ws/output';
+
+export const all = [inline, external, output];
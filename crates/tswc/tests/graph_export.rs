@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use tempfile::TempDir;
+use tswc::apis::{transform, TransformGraphOptions, TransformOptions};
+
+fn fixture() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/graph")
+}
+
+/// Build fixture and read graph written into default path of output dir
+fn export(graph: TransformGraphOptions) -> String {
+  let output = TempDir::new().unwrap();
+  let ext = graph.format.clone().unwrap_or("json".into());
+  transform(TransformOptions {
+    root: fixture().to_str().unwrap().to_string(),
+    output: Some(output.path().to_str().unwrap().to_string()),
+    entries: Some(vec!["src/index.ts".into()]),
+    graph: Some(graph),
+    ..Default::default()
+  })
+  .expect("fixture should compile");
+  fs::read_to_string(output.path().join(format!("tswc-graph.{}", ext)))
+    .expect("graph should be written into output dir")
+}
+
+fn graph_options() -> TransformGraphOptions {
+  TransformGraphOptions {
+    format: None,
+    path: None,
+    collapse_packages: None,
+    root: None,
+    depth: None,
+  }
+}
+
+/// Labels of nodes and `from -> to (kind)` of edges, ids are replaced by labels for readability
+fn summary(json: &str) -> (Vec<String>, Vec<String>) {
+  let graph: Value = serde_json::from_str(json).unwrap();
+  let label = |id: &Value| {
+    graph["nodes"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .find(|f| f["id"] == *id)
+      .map(|f| f["label"].as_str().unwrap().to_string())
+      .expect("edge should point to exported node")
+  };
+  let nodes = graph["nodes"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|f| {
+      format!(
+        "{} ({})",
+        f["label"].as_str().unwrap(),
+        f["kind"].as_str().unwrap()
+      )
+    })
+    .collect();
+  let edges = graph["edges"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|f| {
+      format!(
+        "{} -> {} ({})",
+        label(&f["from"]),
+        label(&f["to"]),
+        f["kind"].as_str().unwrap()
+      )
+    })
+    .collect();
+  (nodes, edges)
+}
+
+#[test]
+fn export_json() {
+  let (nodes, edges) = summary(&export(graph_options()));
+  assert_eq!(
+    nodes,
+    [
+      "node_modules/pkg/index.js (node_modules)",
+      "node_modules/pkg/lib/util.js (node_modules)",
+      "src/a.ts (script)",
+      "src/b.ts (script)",
+      "src/index.ts (script)",
+      "src/lazy.ts (script)",
+    ]
+  );
+  assert_eq!(
+    edges,
+    [
+      "src/a.ts -> src/b.ts (static)",
+      "src/index.ts -> node_modules/pkg/index.js (static)",
+      "src/index.ts -> node_modules/pkg/lib/util.js (static)",
+      "src/index.ts -> src/a.ts (static)",
+      "src/index.ts -> src/lazy.ts (dynamic)",
+    ]
+  );
+}
+
+#[test]
+fn collapse_packages() {
+  let (nodes, edges) = summary(&export(TransformGraphOptions {
+    collapse_packages: Some(true),
+    ..graph_options()
+  }));
+  assert_eq!(
+    nodes
+      .iter()
+      .filter(|f| f.contains("pkg"))
+      .collect::<Vec<_>>(),
+    ["pkg (node_modules)"]
+  );
+  // Both specifiers of the package are kept in JSON
+  assert_eq!(
+    edges
+      .iter()
+      .filter(|f| *f == "src/index.ts -> pkg (static)")
+      .count(),
+    2
+  );
+}
+
+#[test]
+fn export_dot() {
+  let dot = export(TransformGraphOptions {
+    format: Some("dot".into()),
+    collapse_packages: Some(true),
+    ..graph_options()
+  });
+  let src = fixture().join("src");
+  let id = |file: &str| src.join(file).to_str().unwrap().to_string();
+  let expected = [
+    "digraph modules {".to_string(),
+    format!("  \"{}\" [label=\"src/a.ts\" shape=box];", id("a.ts")),
+    format!("  \"{}\" [label=\"src/b.ts\" shape=box];", id("b.ts")),
+    format!(
+      "  \"{}\" [label=\"src/index.ts\" shape=box];",
+      id("index.ts")
+    ),
+    format!("  \"{}\" [label=\"src/lazy.ts\" shape=box];", id("lazy.ts")),
+    "  \"pkg\" [label=\"pkg\" shape=component];".to_string(),
+    format!("  \"{}\" -> \"{}\";", id("a.ts"), id("b.ts")),
+    format!("  \"{}\" -> \"{}\";", id("index.ts"), id("a.ts")),
+    format!(
+      "  \"{}\" -> \"{}\" [style=dashed];",
+      id("index.ts"),
+      id("lazy.ts")
+    ),
+    format!("  \"{}\" -> \"pkg\";", id("index.ts")),
+    "}".to_string(),
+  ];
+  assert_eq!(dot, expected.join("\n"));
+}
+
+#[test]
+fn filter_by_root() {
+  let (nodes, edges) = summary(&export(TransformGraphOptions {
+    root: Some("src/a.ts".into()),
+    ..graph_options()
+  }));
+  assert_eq!(nodes, ["src/a.ts (script)", "src/b.ts (script)"]);
+  assert_eq!(edges, ["src/a.ts -> src/b.ts (static)"]);
+}
+
+#[test]
+fn filter_by_depth() {
+  let (nodes, edges) = summary(&export(TransformGraphOptions {
+    collapse_packages: Some(true),
+    root: Some("src/index.ts".into()),
+    depth: Some(1),
+    ..graph_options()
+  }));
+  assert_eq!(
+    nodes,
+    [
+      "src/a.ts (script)",
+      "src/index.ts (script)",
+      "src/lazy.ts (script)",
+      "pkg (node_modules)",
+    ]
+  );
+  assert!(edges.iter().all(|f| f.starts_with("src/index.ts -> ")));
+}
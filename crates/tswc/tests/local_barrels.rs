@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+use tswc::apis::{transform, TransformOptimizeOptions, TransformOptions};
+
+fn fixture() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/local_barrels")
+}
+
+/// Compile fixture from `index.ts` into a temporary output dir, returns the output dir
+fn compile(local_barrels: Option<bool>) -> TempDir {
+  let output = TempDir::new().unwrap();
+  transform(TransformOptions {
+    root: fixture().to_str().unwrap().to_string(),
+    output: Some(output.path().to_str().unwrap().to_string()),
+    entries: Some(vec!["src/index.ts".into()]),
+    optimize: TransformOptimizeOptions {
+      local_barrels,
+      ..Default::default()
+    },
+    ..Default::default()
+  })
+  .expect("fixture should compile");
+  output
+}
+
+fn read(output: &Path, file: &str) -> String {
+  fs::read_to_string(output.join(file)).unwrap_or_else(|_| panic!("{} should be emitted", file))
+}
+
+#[test]
+fn rewrite_pure_local_barrels() {
+  let expected = fs::read_to_string(fixture().join("expected/index.js")).unwrap();
+  let output = compile(Some(true));
+  assert_eq!(read(output.path(), "index.js"), expected);
+  // Barrel with side effect import is kept, so setup still runs
+  assert!(output.path().join("setup/register.js").exists());
+}
+
+#[test]
+fn skip_type_only_imports() {
+  let output = compile(Some(true));
+  assert!(!output.path().join("theme").exists());
+}
+
+#[test]
+fn local_barrels_are_opt_in() {
+  let output = read(compile(None).path(), "index.js");
+  assert!(
+    output.contains("from \"./components/index.js\""),
+    "{}",
+    output
+  );
+  assert!(output.contains("from \"./setup/index.js\""), "{}", output);
+}
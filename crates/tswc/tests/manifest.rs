@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tswc::apis::{transform, TransformOptions};
+
+#[test]
+fn write_manifest() {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/manifest");
+  let output = TempDir::new().unwrap();
+  transform(TransformOptions {
+    root: root.to_str().unwrap().to_string(),
+    output: Some(output.path().to_str().unwrap().to_string()),
+    entries: Some(vec!["src/index.ts".into()]),
+    manifest: Some(true),
+    ..Default::default()
+  })
+  .expect("fixture should compile");
+  let content = fs::read_to_string(output.path().join("tswc-manifest.json"))
+    .expect("manifest should be written into output dir");
+  let manifest: Value = serde_json::from_str(&content).unwrap();
+
+  let src = |file: &str| root.join("src").join(file).to_str().unwrap().to_string();
+  let out = |file: &str| output.path().join(file).to_str().unwrap().to_string();
+  let script = |file: &str, emitted: &str| json!({ "source": src(file), "output": out(emitted), "kind": "script", "entry": false, "imports": [] });
+  let expected = json!({
+    "input": root.join("src").to_str().unwrap(),
+    "output": output.path().to_str().unwrap(),
+    "modules": [
+      {
+        "source": src("index.ts"),
+        "output": out("index.js"),
+        "kind": "script",
+        "entry": true,
+        "imports": [
+          {
+            "specifier": "fs",
+            "kind": "static",
+            "names": ["readFileSync"],
+            "resolved": "node:fs",
+            "rewritten": "fs"
+          },
+          {
+            "specifier": "./math",
+            "kind": "static",
+            "names": ["add", "sub"],
+            "resolved": src("math.ts"),
+            "rewritten": "./math.js"
+          },
+          {
+            "specifier": "./version",
+            "kind": "re_export",
+            "names": ["version"],
+            "resolved": src("version.ts"),
+            "rewritten": "./version.js"
+          },
+          {
+            "specifier": "./lazy.js",
+            "kind": "dynamic",
+            "names": [],
+            "resolved": src("lazy.ts"),
+            "rewritten": null
+          }
+        ]
+      },
+      script("lazy.ts", "lazy.js"),
+      script("math.ts", "math.js"),
+      script("version.ts", "version.js"),
+      {
+        "source": "node:fs",
+        "output": null,
+        "kind": "builtin",
+        "entry": false,
+        "imports": []
+      }
+    ]
+  });
+  assert_eq!(manifest, expected);
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::Path;
+
+use tempfile::TempDir;
+use tswc::apis::{transform, TransformOptions, TransformResult};
+
+/// Compile fixture with tsconfig `module` set to CommonJS into a temporary output dir
+fn compile() -> (TempDir, TransformResult) {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/module_kind");
+  let output = TempDir::new().unwrap();
+  let result = transform(TransformOptions {
+    root: root.to_str().unwrap().to_string(),
+    output: Some(output.path().to_str().unwrap().to_string()),
+    entries: Some(vec!["src/index.ts".into()]),
+    ..Default::default()
+  })
+  .expect("fixture should compile");
+  (output, result)
+}
+
+#[test]
+fn module_kind_follows_extension() {
+  let (output, _) = compile();
+  let read = |file: &str| {
+    fs::read_to_string(output.path().join(file))
+      .unwrap_or_else(|_| panic!("{} should be emitted", file))
+  };
+  assert_eq!(read("esm.mjs"), "export const esm = 'esm';\n");
+  assert!(read("legacy.cjs").contains("Object.defineProperty(exports, \"cjs\""));
+  assert!(read("index.js").contains("require(\"./legacy.cjs\")"));
+}
+
+#[test]
+fn report_require_of_es_module() {
+  let (_output, result) = compile();
+  let mut diagnostics: Vec<_> = result
+    .diagnostics
+    .iter()
+    .filter(|f| f.code == "require-esm")
+    .collect();
+  diagnostics.sort_by_key(|f| f.line);
+  // `import()` is compiled to `require` as well
+  assert_eq!(diagnostics.len(), 2);
+  assert!(diagnostics.iter().all(|f| f.severity == "error"));
+  assert_eq!(diagnostics[0].line, Some(1));
+  assert!(diagnostics[0].message.contains("`./esm.mjs`"));
+  assert_eq!(diagnostics[1].line, Some(5));
+  assert!(diagnostics[1].message.contains("`./lazy.mjs`"));
+}
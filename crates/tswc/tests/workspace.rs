@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use tempfile::TempDir;
+use tswc::apis::{transform, TransformOptions, TransformResult, TransformWorkspacePackageOptions};
+
+/// Compile fixture with `paths` mapped workspace packages into a temporary output dir
+fn compile(packages: &[(&str, &str)]) -> (TempDir, TransformResult) {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspace");
+  let output = TempDir::new().unwrap();
+  let workspace_packages: HashMap<String, TransformWorkspacePackageOptions> = packages
+    .iter()
+    .map(|(name, strategy)| {
+      let options = TransformWorkspacePackageOptions {
+        strategy: Some(strategy.to_string()),
+        dest: (*strategy == "inline").then(|| format!("vendor/{}", name)),
+      };
+      (name.to_string(), options)
+    })
+    .collect();
+  let result = transform(TransformOptions {
+    root: root.to_str().unwrap().to_string(),
+    output: Some(output.path().to_str().unwrap().to_string()),
+    entries: Some(vec!["src/index.ts".into()]),
+    workspace_packages: Some(workspace_packages),
+    ..Default::default()
+  })
+  .expect("fixture should compile");
+  (output, result)
+}
+
+fn compile_index() -> (TempDir, String) {
+  let (output, _) = compile(&[
+    ("@ws/inline", "inline"),
+    ("@ws/external", "external"),
+    ("@ws/output", "output"),
+  ]);
+  let index = fs::read_to_string(output.path().join("index.js")).unwrap();
+  (output, index)
+}
+
+#[test]
+fn inline_into_dest() {
+  let (output, index) = compile_index();
+  let inlined = output.path().join("vendor/@ws/inline/src/index.js");
+  assert_eq!(
+    fs::read_to_string(inlined).expect("inlined source should land under dest"),
+    "export const inline = 'inline';\n"
+  );
+  assert!(index.contains("import { inline } from \"./vendor/@ws/inline/src/index.js\";"));
+}
+
+#[test]
+fn keep_external_specifier() {
+  let (output, index) = compile_index();
+  assert!(index.contains("import { external } from \"@ws/external\";"));
+  assert!(!output.path().join("vendor/@ws/external").exists());
+}
+
+#[test]
+fn rewrite_to_built_output() {
+  let (_output, index) = compile_index();
+  // Importer is emitted as ES module so `import` condition of package exports is picked
+  let line = index
+    .lines()
+    .find(|f| f.starts_with("import { output }"))
+    .unwrap();
+  assert!(line.ends_with("/tests/fixtures/workspace/packages/output/dist/index.mjs\";"));
+}
+
+#[test]
+fn report_missing_workspace_package() {
+  let (_output, result) = compile(&[("@ws/missing", "inline")]);
+  let diagnostics: Vec<_> = result
+    .diagnostics
+    .iter()
+    .filter(|f| f.code == "workspace-package-not-found")
+    .collect();
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(diagnostics[0].severity, "error");
+  assert!(diagnostics[0].message.contains("@ws/missing"));
+}